@@ -1,6 +1,20 @@
+use crate::family_menu::FamilyMenu;
+use crate::game::Game;
+use crate::layout_tool::LayoutTool;
+use crate::level_select::LevelSelect;
+use crate::path_finder::PathFinder;
+use crate::playback::Playback;
+use crate::town_generator::{TownDialog, TownUse};
 use egui;
 use egui_extras::RetainedImage;
+use egui_file::{DialogType, FileDialog};
 use graph::graph::Graph;
+use graph::history::Revision;
+use graph::level::Level;
+use graph::proximity::ProximityGraph;
+use graph::save::GraphData;
+use graph::spanning_tree::{SpanningForest, SpanningTreeAlgorithm};
+use graph::weighting::Weighting;
 use std::collections::HashMap;
 
 pub struct App {
@@ -8,20 +22,77 @@ pub struct App {
     show_graph: bool,
 
     covering_tree: Graph,
+    /// Spanning forest of the covering tree, with what they were computed from
+    spanning_forest: Option<(CoveringTreeKey, SpanningForest)>,
     show_covering_tree: bool,
     min_covering_tree_algorithm: bool,
+    spanning_tree_algorithm: SpanningTreeAlgorithm,
+    /// The covering tree only spans the selected nodes, if there are some
+    selection_covering_tree: bool,
+    /// Every connected component is drawn with its own colour
+    color_components: bool,
+    /// Bridges and articulation points are highlighted on the graph and the covering tree
+    show_critical_parts: bool,
+    /// Graph added by the connect button, built from the positions of the houses
+    proximity_graph: ProximityGraph,
+    playback: Playback,
+    path_finder: PathFinder,
+    family_menu: FamilyMenu,
+    layout_tool: LayoutTool,
+
+    game: Game,
+    level_select: LevelSelect,
+    town_dialog: TownDialog,
 
     width_node: f32,
     width_vertex: f32,
     node_radius: f32,
     width_image: f32,
     width_cobblestone: f32,
+    show_weights: bool,
+    weight_font_size: f32,
 
     show_ui: bool,
 
     dark_mode: bool,
 
+    /// Dialog to save or open a graph file
+    file_dialog: Option<FileDialog>,
+    file_error: Option<String>,
+
     textures: HashMap<String, RetainedImage>,
+    /// Name of every texture with its id, to save and load graphs
+    texture_ids: Vec<(String, egui::TextureId)>,
+}
+
+/// What the covering tree is computed from, it is computed again only when this changes
+#[derive(Debug, Clone, PartialEq)]
+struct CoveringTreeKey {
+    revision: Revision,
+    algorithm: SpanningTreeAlgorithm,
+    max: bool,
+    /// Nodes spanned by the covering tree, every node if it is empty
+    selection: Vec<usize>,
+}
+
+/// Part of the app restored between sessions
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SavedState {
+    graph: GraphData,
+    show_graph: bool,
+    show_covering_tree: bool,
+    min_covering_tree_algorithm: bool,
+    spanning_tree_algorithm: SpanningTreeAlgorithm,
+
+    width_node: f32,
+    width_vertex: f32,
+    node_radius: f32,
+    width_image: f32,
+    width_cobblestone: f32,
+    show_weights: bool,
+    weight_font_size: f32,
+
+    dark_mode: bool,
 }
 
 impl Default for App {
@@ -75,6 +146,7 @@ impl Default for App {
             show_graph: true,
 
             covering_tree: Graph::new_default_with_color(egui::Color32::LIGHT_RED),
+            spanning_forest: None,
             show_covering_tree: false,
 
             width_node: 10.0,
@@ -82,38 +154,180 @@ impl Default for App {
             node_radius: 20.0,
             width_image: 100.0,
             width_cobblestone: 50.0,
+            show_weights: false,
+            weight_font_size: 14.0,
 
             min_covering_tree_algorithm: false,
+            spanning_tree_algorithm: SpanningTreeAlgorithm::default(),
+            selection_covering_tree: false,
+            color_components: false,
+            show_critical_parts: false,
+            proximity_graph: ProximityGraph::default(),
+            playback: Playback::default(),
+            path_finder: PathFinder::default(),
+            family_menu: FamilyMenu::default(),
+            layout_tool: LayoutTool::default(),
+
+            game: Game::default(),
+            level_select: LevelSelect::default(),
+            town_dialog: TownDialog::default(),
 
             show_ui: true,
 
             dark_mode: true,
 
+            file_dialog: None,
+            file_error: None,
+
             textures,
+            texture_ids: Vec::new(),
         }
     }
 }
 
 impl App {
     /// Called once before the first frame.
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         // This is also where you can customize the look and feel of egui using
         // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+        let mut app: App = Default::default();
+        app.texture_ids = texture_names(&app.textures, &cc.egui_ctx);
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        // if let Some(storage) = cc.storage {
-        //     return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        //}
-        Default::default()
+        if let Some(storage) = cc.storage {
+            if let Some(state) = eframe::get_value::<SavedState>(storage, eframe::APP_KEY) {
+                app.restore(state);
+            }
+        }
+
+        if app.dark_mode {
+            cc.egui_ctx.set_visuals(egui::Visuals::dark());
+        } else {
+            cc.egui_ctx.set_visuals(egui::Visuals::light());
+        }
+        app
     }
 
     pub fn set_default(&mut self) {
+        let texture_ids = std::mem::take(&mut self.texture_ids);
         *self = Default::default();
+        self.texture_ids = texture_ids;
+    }
+
+    fn saved_state(&self) -> SavedState {
+        SavedState {
+            graph: self.graph.to_data(self.texture_ids.as_slice()),
+            show_graph: self.show_graph,
+            show_covering_tree: self.show_covering_tree,
+            min_covering_tree_algorithm: self.min_covering_tree_algorithm,
+            spanning_tree_algorithm: self.spanning_tree_algorithm,
+
+            width_node: self.width_node,
+            width_vertex: self.width_vertex,
+            node_radius: self.node_radius,
+            width_image: self.width_image,
+            width_cobblestone: self.width_cobblestone,
+            show_weights: self.show_weights,
+            weight_font_size: self.weight_font_size,
+
+            dark_mode: self.dark_mode,
+        }
+    }
+
+    fn restore(&mut self, state: SavedState) {
+        // a graph that does not refer to its own nodes is not restored
+        if state.graph.check().is_ok() {
+            self.graph = Graph::from_data(state.graph, self.texture_ids.as_slice());
+        }
+        self.show_graph = state.show_graph;
+        self.show_covering_tree = state.show_covering_tree;
+        self.min_covering_tree_algorithm = state.min_covering_tree_algorithm;
+        self.spanning_tree_algorithm = state.spanning_tree_algorithm;
+
+        self.width_node = state.width_node;
+        self.width_vertex = state.width_vertex;
+        self.node_radius = state.node_radius;
+        self.width_image = state.width_image;
+        self.width_cobblestone = state.width_cobblestone;
+        self.show_weights = state.show_weights;
+        self.weight_font_size = state.weight_font_size;
+
+        self.dark_mode = state.dark_mode;
+    }
+}
+
+/// Name of every texture of the app with its id, to save and load graphs
+fn texture_names(
+    textures: &HashMap<String, RetainedImage>,
+    ctx: &egui::Context,
+) -> Vec<(String, egui::TextureId)> {
+    textures
+        .iter()
+        .map(|(name, image)| (name.clone(), image.texture_id(ctx)))
+        .collect()
+}
+
+/// Textures of the cobblestone roads
+fn cobblestone_textures(
+    textures: &HashMap<String, RetainedImage>,
+    ctx: &egui::Context,
+) -> Vec<egui::TextureId> {
+    vec![
+        textures.get("cobblestone1").unwrap().texture_id(ctx),
+        textures.get("cobblestone2").unwrap().texture_id(ctx),
+    ]
+}
+
+/// Graph of a town with the textures of its houses and roads
+fn level_graph(
+    level: &Level,
+    textures: &HashMap<String, RetainedImage>,
+    width_image: f32,
+    ctx: &egui::Context,
+) -> Graph {
+    let mut graph = level.to_graph(egui::Stroke::new(2.0, egui::Color32::GREEN));
+    for (id, house) in level.houses.iter().enumerate() {
+        if let Some(texture) = house.texture.as_ref().and_then(|name| textures.get(name)) {
+            graph.set_texture_node(
+                id,
+                width_image,
+                texture.texture_id(ctx),
+                texture.size_vec2(),
+            );
+        }
     }
+    if level.cobblestone {
+        graph.set_textures_vertex(cobblestone_textures(textures, ctx));
+    }
+    graph.clear_history();
+    graph
+}
+
+fn save_graph(
+    graph: &Graph,
+    path: &std::path::Path,
+    textures: &[(String, egui::TextureId)],
+) -> Result<(), String> {
+    let json = graph.to_json(textures).map_err(|error| error.to_string())?;
+    std::fs::write(path, json).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+fn open_graph(
+    path: &std::path::Path,
+    textures: &[(String, egui::TextureId)],
+) -> Result<Graph, String> {
+    let json =
+        std::fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+    Graph::from_json(&json, textures).map_err(|error| format!("{}: {error}", path.display()))
 }
 
 impl eframe::App for App {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.saved_state());
+    }
+
     /// Called each time the UI needs repainting, which may be many times per second.
     /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
@@ -122,6 +336,7 @@ impl eframe::App for App {
             show_graph,
 
             covering_tree,
+            spanning_forest,
             show_covering_tree,
 
             width_node,
@@ -129,19 +344,72 @@ impl eframe::App for App {
             node_radius,
             width_image,
             width_cobblestone,
+            show_weights,
+            weight_font_size,
 
             min_covering_tree_algorithm,
+            spanning_tree_algorithm,
+            selection_covering_tree,
+            color_components,
+            show_critical_parts,
+            proximity_graph,
+            playback,
+            path_finder,
+            family_menu,
+            layout_tool,
+
+            game,
+            level_select,
+            town_dialog,
 
             show_ui,
 
             dark_mode,
 
+            file_dialog,
+            file_error,
+
             textures,
+            texture_ids,
         } = self;
 
+        // the camera is moved after the panels are shown, to fit the space left by them
+        let mut fit_to_view = false;
+
         //// update APP VALUE
-        if *show_covering_tree {
-            *covering_tree = graph.covering_tree(*min_covering_tree_algorithm);
+        // the town of the game does not move, and nothing in it is selected or inspected
+        if game.is_active() {
+            layout_tool.stop(graph);
+            graph.set_selected_vertex(None).set_selected_nodes(&[]);
+        } else {
+            layout_tool.update(ctx, graph);
+        }
+
+        // the shortest path is compared with the one inside the covering tree
+        if *show_covering_tree || path_finder.is_active() || layout_tool.is_tree_view_open() {
+            let key = CoveringTreeKey {
+                revision: graph.revision(),
+                algorithm: *spanning_tree_algorithm,
+                max: *min_covering_tree_algorithm,
+                selection: if *selection_covering_tree {
+                    graph.selected_nodes()
+                } else {
+                    Vec::new()
+                },
+            };
+            if spanning_forest.as_ref().map(|(computed, _)| computed) != Some(&key) {
+                let subgraph;
+                let spanned = if key.selection.is_empty() {
+                    &*graph
+                } else {
+                    subgraph = graph.subgraph(&key.selection);
+                    &subgraph
+                };
+                *covering_tree = spanned.covering_tree(key.algorithm, key.max);
+                let forest = spanned.spanning_forest(key.algorithm, key.max);
+                *spanning_forest = Some((key, forest));
+            }
+            covering_tree.set_camera(graph.camera());
         }
 
         // every node is update with the same radius for the two graph
@@ -150,6 +418,7 @@ impl eframe::App for App {
             .set_width_nodes(*width_node)
             .set_radius_nodes(*node_radius)
             .set_width_cobblestone_vertex(*width_cobblestone)
+            .set_label_size_vertex(show_weights.then_some(*weight_font_size))
             .set_color_nodes(egui::Color32::LIGHT_GREEN)
             .set_color_vertex(egui::Color32::LIGHT_GREEN); // lourd en calcul pour rien
 
@@ -158,29 +427,155 @@ impl eframe::App for App {
             .set_width_nodes(*width_node)
             .set_radius_nodes(*node_radius)
             .set_width_cobblestone_vertex(*width_cobblestone)
+            .set_label_size_vertex(show_weights.then_some(*weight_font_size))
             .set_color_nodes(egui::Color32::LIGHT_RED)
             .set_color_vertex(egui::Color32::LIGHT_RED); // lourd en calcul pour rien
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // The central panel the region left after adding TopPanel's and SidePanel's
-            egui::warn_if_debug_build(ui);
+        // the town of the game cannot be edited, so there is nothing to undo,
+        // and the nodes being laid out are not edited before the end of the animation
+        if !game.is_active() && !layout_tool.is_animating() && !ctx.wants_keyboard_input() {
+            let redo = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+            if ctx.input_mut(|i| i.consume_key(redo, egui::Key::Z)) {
+                graph.redo();
+            } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+                graph.undo();
+            }
+        }
 
-            // Draw the App
-            if *show_graph {
-                graph.draw(ui);
+        // the game, the playback and the shortest path all colour the vertices of the graph
+        if game.is_active() {
+            if playback.is_active() {
+                playback.stop(graph);
             }
-            if *show_covering_tree {
-                covering_tree.draw(ui);
+            if path_finder.is_active() {
+                path_finder.stop(graph, covering_tree);
+            }
+            game.update(graph);
+        } else if path_finder.is_active() {
+            if playback.is_active() {
+                playback.stop(graph);
+            }
+            path_finder.update(graph, covering_tree);
+        } else if playback.is_active() {
+            playback.update(ctx, graph);
+        } else if *color_components || *show_critical_parts {
+            graph.clear_highlights();
+            if *color_components {
+                graph.highlight_components();
+            }
+            // drawn over the components
+            if *show_critical_parts {
+                graph.highlight_critical_parts();
             }
+        }
+        // the covering tree is kept between frames, its highlights are set at every frame
+        if !path_finder.is_active() {
+            covering_tree.clear_highlights();
+            if *show_critical_parts && !game.is_active() {
+                covering_tree.highlight_critical_parts();
+            }
+        }
 
-            // Handle graph events
-            let events = ui.input(|i| i.clone().events);
-            for event in events.iter() {
-                if *show_graph || *show_covering_tree {
-                    graph.update(event);
+        if let (false, Some((id1, id2))) = (game.is_active(), graph.selected_vertex()) {
+            let mut open = true;
+            egui::Window::new("Selected vertex")
+                .open(&mut open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    let distance = graph.distance(id1, id2).unwrap_or_default();
+                    let explicit_weight = graph.explicit_weight(id1, id2);
+                    let mut is_explicit = explicit_weight.is_some();
+                    let mut weight = explicit_weight.unwrap_or(distance);
+
+                    ui.label(format!("Vertex ({id1}, {id2}) of length {distance:.1}"));
+                    if ui.checkbox(&mut is_explicit, "Explicit weight").changed() {
+                        graph.set_weight(id1, id2, is_explicit.then_some(weight));
+                    }
+                    // the whole drag, or the whole typing, of the weight is a single step
+                    let response = ui.add_enabled(
+                        is_explicit,
+                        egui::DragValue::new(&mut weight).clamp_range(0.0..=f32::MAX),
+                    );
+                    if response.changed() {
+                        graph.drag_weight(id1, id2, Some(weight));
+                    }
+                    if response.drag_released() || response.lost_focus() {
+                        graph.end_weight_drag();
+                    }
+
+                    if graph.weighting() == Weighting::Geometric {
+                        ui.label("Explicit weights are only used with the explicit weighting");
+                    }
+
+                    ui.separator();
+                    let mut cobblestone = graph.is_cobblestone(id1, id2);
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut cobblestone, false, "Straight");
+                        ui.radio_value(&mut cobblestone, true, "Cobblestone");
+                    });
+                    if cobblestone != graph.is_cobblestone(id1, id2) {
+                        if cobblestone {
+                            graph.set_textures(id1, id2, cobblestone_textures(textures, ctx));
+                        } else {
+                            graph.set_straight(
+                                id1,
+                                id2,
+                                egui::Stroke::new(*width_vertex, egui::Color32::LIGHT_GREEN),
+                            );
+                        }
+                    }
+
+                    if ui.button("Delete the vertex").clicked() {
+                        graph.rm_edge(id1, id2);
+                    }
+                });
+            if !open {
+                graph.end_weight_drag();
+                graph.set_selected_vertex(None);
+            }
+        }
+
+        if let Some(level) = level_select.ui(ctx) {
+            *graph = level_graph(&level, textures, *width_image, ctx);
+            game.start(level.name, ctx.input(|i| i.time));
+            fit_to_view = true;
+        }
+        layout_tool.tree_view(ctx, covering_tree);
+
+        if let Some((level, town_use)) = town_dialog.ui(ctx) {
+            if game.is_active() {
+                game.stop(graph);
+            }
+            *graph = level_graph(&level, textures, *width_image, ctx);
+            if town_use == TownUse::Play {
+                game.start(level.name, ctx.input(|i| i.time));
+            }
+            fit_to_view = true;
+        }
+
+        if let Some(dialog) = file_dialog {
+            if dialog.show(ctx).selected() {
+                if let Some(path) = dialog.path() {
+                    let result = match dialog.dialog_type() {
+                        DialogType::SaveFile => save_graph(graph, &path, texture_ids),
+                        _ => open_graph(&path, texture_ids).map(|loaded| {
+                            *graph = loaded;
+                            fit_to_view = true;
+                        }),
+                    };
+                    *file_error = result.err();
                 }
             }
-        });
+        }
+        if let Some(error) = file_error.clone() {
+            let mut open = true;
+            egui::Window::new("File error")
+                .open(&mut open)
+                .show(ctx, |ui| ui.colored_label(egui::Color32::RED, error));
+            if !open {
+                *file_error = None;
+            }
+        }
 
         egui::Window::new("UI")
             .resizable(false)
@@ -191,10 +586,51 @@ impl eframe::App for App {
 
         if *show_ui {
             egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            if ui.button("Reset Graph and State").clicked()
-                || ui.input(|i| i.key_pressed(egui::Key::R))
+            // the town of the game and the nodes being laid out cannot be edited
+            let editable = !game.is_active() && !layout_tool.is_animating();
+            if ui
+                .add_enabled(editable, egui::Button::new("Reset Graph and State"))
+                .clicked()
+                || (editable
+                    && !ctx.wants_keyboard_input()
+                    && ui.input(|i| i.key_pressed(egui::Key::R)))
             {
-                *graph = Graph::default();
+                graph.clear();
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(editable && graph.can_undo(), egui::Button::new("Undo"))
+                    .on_hover_text("Ctrl+Z")
+                    .clicked()
+                {
+                    graph.undo();
+                }
+                if ui
+                    .add_enabled(editable && graph.can_redo(), egui::Button::new("Redo"))
+                    .on_hover_text("Ctrl+Shift+Z")
+                    .clicked()
+                {
+                    graph.redo();
+                }
+            });
+
+            // there is no file system in the browser
+            if cfg!(not(target_arch = "wasm32")) {
+                ui.horizontal(|ui| {
+                    if ui.button("Open").clicked() {
+                        let mut dialog = FileDialog::open_file(None).filter(Box::new(|path| {
+                            path.extension().map_or(false, |extension| extension == "json")
+                        }));
+                        dialog.open();
+                        *file_dialog = Some(dialog);
+                    }
+                    if ui.button("Save").clicked() {
+                        let mut dialog = FileDialog::save_file(None).default_filename("graph.json");
+                        dialog.open();
+                        *file_dialog = Some(dialog);
+                    }
+                });
             }
 
             // button to change the theme of the app
@@ -207,10 +643,22 @@ impl eframe::App for App {
                 }
             }
 
+            if ui.button("Fit graph to view").clicked() {
+                fit_to_view = true;
+            }
+
+            ui.label("To zoom use the mouse wheel, to move around drag with the middle button or with space and the left button");
             ui.label("To add a node click the button add Node and then press A where you want to add the node");
             ui.label("To add an edge right click on a first edge then a second");
             ui.label("To move a node around left click on it");
+            ui.label("To select several nodes shift click on them or draw a rectangle around them, then drag one of them to move them all");
+            ui.label("To remove a node press Delete with the mouse on it");
+            ui.label("To select an edge, change its weight or its style, left click on it");
+            ui.label("To remove the selected edge press Delete");
             ui.label("To show the covering tree or the graph you can tick or untick the checkbox");
+            ui.label("To place the nodes automatically choose a layout in Layout and click Apply the layout");
+            ui.label("To insert a standard graph choose it in Standard graphs, click Insert and then click where it goes");
+            ui.label("To find the shortest path between two houses click Pick two houses in Shortest path, then click on them");
         });
 
             egui::SidePanel::left("side_panel").show(ctx, |ui| {
@@ -219,10 +667,233 @@ impl eframe::App for App {
                     "min or max covering tree",
                 ));
 
-                if ui.button("Add every edge to the graph").clicked() {
-                    graph.add_every_edge();
+                ui.horizontal(|ui| {
+                    ui.label("Weighting");
+                    for weighting in Weighting::ALL {
+                        if ui
+                            .radio(graph.weighting() == weighting, format!("{weighting}"))
+                            .clicked()
+                        {
+                            graph.set_weighting(weighting);
+                        }
+                    }
+                });
+
+                egui::ComboBox::from_label("Algorithm")
+                    .selected_text(format!("{spanning_tree_algorithm}"))
+                    .show_ui(ui, |ui| {
+                        for algorithm in SpanningTreeAlgorithm::ALL {
+                            ui.selectable_value(
+                                spanning_tree_algorithm,
+                                algorithm,
+                                format!("{algorithm}"),
+                            );
+                        }
+                    });
+                if let (true, Some((_, forest))) = (*show_covering_tree, &*spanning_forest) {
+                    ui.label(format!("Covering tree weight: {:.1}", forest.total()));
+                    if !forest.is_tree() {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!(
+                                "The town is split in {} groups that cannot be connected, \
+                                 this is a spanning forest",
+                                forest.components.count()
+                            ),
+                        );
+                        for (label, total) in forest.totals.iter().enumerate() {
+                            ui.label(format!(
+                                "Group {label} ({} houses): {total:.1}",
+                                forest.components.sizes()[label]
+                            ));
+                        }
+                    }
+                }
+                if ui
+                    .checkbox(color_components, "Colour the connected components")
+                    .changed()
+                {
+                    graph.clear_highlights();
+                }
+                if ui
+                    .checkbox(show_critical_parts, "Show bridges and articulation points")
+                    .on_hover_text("The roads and the houses that split the town when removed")
+                    .changed()
+                {
+                    graph.clear_highlights();
+                }
+                if *show_critical_parts {
+                    let parts = graph.critical_parts();
+                    ui.label(format!(
+                        "Graph: {} bridges and {} articulation points",
+                        parts.bridges.len(),
+                        parts.articulation_points.len()
+                    ));
+                    if *show_covering_tree {
+                        let parts = covering_tree.critical_parts();
+                        ui.label(format!(
+                            "Covering tree: {} bridges out of {} vertices and {} articulation points",
+                            parts.bridges.len(),
+                            covering_tree.n_vertices(),
+                            parts.articulation_points.len()
+                        ));
+                        ui.label("Every vertex of a tree is a bridge: removing any road splits the town");
+                    }
                 }
 
+                ui.collapsing("Muddy City game", |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button("Choose a level").clicked() {
+                            level_select.open = true;
+                        }
+                        if ui.button("Random town").clicked() {
+                            town_dialog.open = true;
+                        }
+                    });
+                    game.ui(ui, graph, "Custom town");
+                });
+
+                ui.collapsing("Step by step", |ui| {
+                    playback.ui(
+                        ui,
+                        graph,
+                        *spanning_tree_algorithm,
+                        *min_covering_tree_algorithm,
+                    );
+                });
+
+                ui.collapsing("Shortest path", |ui| {
+                    path_finder.ui(ui, graph, covering_tree);
+                });
+
+                ui.collapsing("Layout", |ui| {
+                    ui.add_enabled_ui(!game.is_active(), |ui| {
+                        layout_tool.ui(ui, graph);
+                    });
+                });
+
+                ui.collapsing("Standard graphs", |ui| {
+                    family_menu.ui(ui);
+                });
+
+                ui.collapsing("Selection", |ui| {
+                    let selection = graph.selected_nodes();
+                    ui.label(format!("{} selected nodes", selection.len()));
+                    ui.checkbox(
+                        selection_covering_tree,
+                        "Covering tree of the selection only",
+                    );
+                    ui.add_enabled_ui(!selection.is_empty() && !game.is_active(), |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button("Delete").clicked() {
+                                graph.rm_selected_nodes();
+                            }
+                            if ui.button("Connect every pair").clicked() {
+                                graph.connect_selected_nodes();
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Nodes");
+                            if ui.button("Houses").clicked() {
+                                for (i, &id) in selection.iter().enumerate() {
+                                    let house = &textures[&format!("house{}", i % 5 + 1)];
+                                    graph.set_texture_node(
+                                        id,
+                                        *width_image,
+                                        house.texture_id(ctx),
+                                        house.size_vec2(),
+                                    );
+                                }
+                            }
+                            if ui.button("Circles").clicked() {
+                                for &id in selection.iter() {
+                                    graph.set_circle_node(
+                                        id,
+                                        egui::Stroke::new(*width_node, egui::Color32::LIGHT_GREEN),
+                                    );
+                                }
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Vertices");
+                            if ui.button("Straight").clicked() {
+                                for (id1, id2) in graph.selected_vertices() {
+                                    graph.set_straight(
+                                        id1,
+                                        id2,
+                                        egui::Stroke::new(
+                                            *width_vertex,
+                                            egui::Color32::LIGHT_GREEN,
+                                        ),
+                                    );
+                                }
+                            }
+                            if ui.button("Cobblestone").clicked() {
+                                for (id1, id2) in graph.selected_vertices() {
+                                    graph.set_textures(
+                                        id1,
+                                        id2,
+                                        cobblestone_textures(textures, ctx),
+                                    );
+                                }
+                            }
+                        });
+                    });
+                });
+
+                // the town of the game cannot be edited
+                ui.add_enabled_ui(!game.is_active(), |ui| {
+                    if ui.button("Add every edge to the graph").clicked() {
+                        graph.add_every_edge();
+                    }
+                    if ui
+                        .button("Connect by shortest tree")
+                        .on_hover_text("Add the edges of the Euclidean minimum spanning tree, without adding every edge first")
+                        .clicked()
+                    {
+                        graph.connect_euclidean_mst();
+                    }
+                    egui::ComboBox::from_label("Proximity graph")
+                        .selected_text(format!("{proximity_graph}"))
+                        .show_ui(ui, |ui| {
+                            for candidate in ProximityGraph::ALL {
+                                // the parameter is kept while the kind does not change
+                                if ui
+                                    .selectable_label(
+                                        proximity_graph.same_kind(&candidate),
+                                        format!("{candidate}"),
+                                    )
+                                    .clicked()
+                                    && !proximity_graph.same_kind(&candidate)
+                                {
+                                    *proximity_graph = candidate;
+                                }
+                            }
+                        });
+                    match proximity_graph {
+                        ProximityGraph::KNearest { k } => {
+                            ui.add(egui::Slider::new(k, 1..=10).text("Neighbours"));
+                        }
+                        ProximityGraph::UnitDisk { radius } => {
+                            ui.add(egui::Slider::new(radius, 10.0..=1000.0).text("Radius"));
+                        }
+                        _ => {}
+                    }
+                    if ui
+                        .button(format!("Connect by {proximity_graph}"))
+                        .on_hover_text(match proximity_graph {
+                            ProximityGraph::Delaunay => "Add the edges of the Delaunay triangulation, they contain the shortest covering tree",
+                            ProximityGraph::Gabriel => "Join two houses when no other house is in the circle whose diameter is the road between them",
+                            ProximityGraph::RelativeNeighbourhood => "Join two houses when no other house is closer to both of them than they are to each other",
+                            ProximityGraph::KNearest { .. } => "Join every house to its nearest houses",
+                            ProximityGraph::UnitDisk { .. } => "Join every pair of houses closer than the radius",
+                        })
+                        .clicked()
+                    {
+                        graph.connect_proximity(*proximity_graph);
+                    }
+                });
+
                 ui.separator();
 
                 ui.add(egui::Checkbox::new(show_graph, "Show Graph"));
@@ -231,7 +902,9 @@ impl eframe::App for App {
                     "Show covering Tree",
                 ));
 
-                graph.add_node_ui(ui);
+                ui.add_enabled_ui(!game.is_active(), |ui| {
+                    graph.add_node_ui(ui);
+                });
 
                 ui.separator();
 
@@ -244,6 +917,12 @@ impl eframe::App for App {
 
                 ui.add(egui::Slider::new(node_radius, 0.0..=40.0).text("Radius"));
 
+                ui.add(egui::Checkbox::new(show_weights, "Show weights"));
+                ui.add_enabled(
+                    *show_weights,
+                    egui::Slider::new(weight_font_size, 8.0..=40.0).text("Font size weights"),
+                );
+
                 // Button to change the first node of the graph to the flower picture
                 if ui.button("Change firsts nodes to images").clicked() {
                     graph.set_textures_nodes(
@@ -267,10 +946,7 @@ impl eframe::App for App {
 
                 // Button to change the first node of the graph to the flower picture
                 if ui.button("Change every Vertex to Cobblestone").clicked() {
-                    graph.set_textures_vertex(vec![
-                        textures.get("cobblestone1").unwrap().texture_id(ctx),
-                        textures.get("cobblestone2").unwrap().texture_id(ctx),
-                    ]);
+                    graph.set_textures_vertex(cobblestone_textures(textures, ctx));
                 }
 
                 // DEBUG to show the graph
@@ -278,5 +954,111 @@ impl eframe::App for App {
                 covering_tree.debug(ui);
             });
         }
+
+        if fit_to_view {
+            graph.fit_to_view(ctx.available_rect());
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // The central panel the region left after adding TopPanel's and SidePanel's
+            egui::warn_if_debug_build(ui);
+
+            // Draw the App
+            if *show_graph {
+                graph.draw(ui);
+            }
+            if *show_covering_tree {
+                covering_tree.draw(ui);
+            }
+
+            // Handle graph events
+            let events = ui.input(|i| i.clone().events);
+            // the windows are above the graph
+            let canvas = ui.max_rect();
+            let on_canvas = |pos: egui::Pos2| {
+                canvas.contains(pos)
+                    && ctx
+                        .layer_id_at(pos)
+                        .map_or(true, |layer| layer.order == egui::Order::Background)
+            };
+            for event in events.iter() {
+                // the keys typed in a text field are not shortcuts
+                if ctx.wants_keyboard_input() && matches!(event, egui::Event::Key { .. }) {
+                    continue;
+                }
+                // the wheel scrolls the windows instead of zooming
+                if matches!(event, egui::Event::Scroll(_) | egui::Event::Zoom(_))
+                    && !ctx.pointer_hover_pos().map_or(false, on_canvas)
+                {
+                    continue;
+                }
+                // the clicks on the panels and the windows are not for the graph
+                if let egui::Event::PointerButton {
+                    pos, pressed: true, ..
+                } = event
+                {
+                    if !on_canvas(*pos) {
+                        continue;
+                    }
+                }
+                // the click places the standard graph instead of selecting
+                if let egui::Event::PointerButton {
+                    pos,
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    ..
+                } = event
+                {
+                    if family_menu.is_placing() && !game.is_active() && !graph.camera().is_panning()
+                    {
+                        family_menu.place(graph, graph.camera().to_world(*pos));
+                        continue;
+                    }
+                }
+                // the houses of the path are picked instead of being dragged
+                if let egui::Event::PointerButton {
+                    pos,
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    ..
+                } = event
+                {
+                    if path_finder.is_active() && !graph.camera().is_panning() {
+                        if let Some(id) = graph.node_at(graph.camera().to_world(*pos)) {
+                            path_finder.pick(id);
+                            continue;
+                        }
+                    }
+                }
+                // the town cannot be edited while playing or being laid out, but it can be moved
+                if (*show_graph || *show_covering_tree)
+                    && !game.is_active()
+                    && !layout_tool.is_animating()
+                {
+                    graph.update(event);
+                } else {
+                    graph.update_camera(event);
+                }
+                // Click on a vertex to pave it or to select it
+                if let egui::Event::PointerButton {
+                    pos,
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    ..
+                } = event
+                {
+                    if *show_graph && !graph.camera().is_panning() {
+                        let vertex = graph.vertex_at(graph.camera().to_world(*pos));
+                        if game.is_active() {
+                            if let Some((id1, id2)) = vertex {
+                                game.toggle_road(id1, id2);
+                            }
+                        } else {
+                            graph.set_selected_vertex(vertex);
+                        }
+                    }
+                }
+            }
+        });
     }
 }
@@ -0,0 +1,92 @@
+use graph::families::GraphFamily;
+use graph::graph::Graph;
+
+/// Menu to insert a standard graph where the user clicks next
+#[derive(Debug, Clone)]
+pub struct FamilyMenu {
+    family: GraphFamily,
+    /// Distance between the joined nodes
+    spacing: f32,
+    /// The next click on the canvas inserts the graph
+    placing: bool,
+}
+
+impl Default for FamilyMenu {
+    fn default() -> Self {
+        Self {
+            family: GraphFamily::default(),
+            spacing: 120.,
+            placing: false,
+        }
+    }
+}
+
+impl FamilyMenu {
+    pub fn is_placing(&self) -> bool {
+        self.placing
+    }
+
+    /// Insert the graph centered on `pos` and select its nodes to move them together
+    pub fn place(&mut self, graph: &mut Graph, pos: egui::Pos2) {
+        let stroke = egui::Stroke::new(2.0, egui::Color32::GREEN);
+        let family = Graph::family(self.family, pos, self.spacing, stroke);
+        let ids = graph.add_graph(&family, stroke);
+        graph.set_selected_nodes(&ids);
+        self.placing = false;
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Family")
+            .selected_text(format!("{}", self.family))
+            .show_ui(ui, |ui| {
+                for candidate in GraphFamily::ALL {
+                    // the parameters are kept while the family does not change
+                    if ui
+                        .selectable_label(self.family.same_kind(&candidate), format!("{candidate}"))
+                        .clicked()
+                        && !self.family.same_kind(&candidate)
+                    {
+                        self.family = candidate;
+                    }
+                }
+            });
+        match &mut self.family {
+            GraphFamily::Complete { n }
+            | GraphFamily::Cycle { n }
+            | GraphFamily::Path { n }
+            | GraphFamily::Star { n }
+            | GraphFamily::Wheel { n } => {
+                ui.add(egui::Slider::new(n, 1..=30).text("Nodes"));
+            }
+            GraphFamily::Grid { rows, columns } => {
+                ui.add(egui::Slider::new(rows, 1..=15).text("Rows"));
+                ui.add(egui::Slider::new(columns, 1..=15).text("Columns"));
+            }
+            GraphFamily::Hypercube { dimension } => {
+                ui.add(egui::Slider::new(dimension, 0..=6).text("Dimension"));
+            }
+            GraphFamily::Petersen => {}
+            GraphFamily::CompleteBipartite { m, n } => {
+                ui.add(egui::Slider::new(m, 1..=15).text("Left nodes"));
+                ui.add(egui::Slider::new(n, 1..=15).text("Right nodes"));
+            }
+            GraphFamily::RandomTree { n, seed } => {
+                ui.add(egui::Slider::new(n, 1..=50).text("Nodes"));
+                ui.horizontal(|ui| {
+                    ui.label("Seed");
+                    ui.add(egui::DragValue::new(seed));
+                });
+            }
+        }
+        ui.add(egui::Slider::new(&mut self.spacing, 40.0..=300.0).text("Spacing"));
+
+        if self.placing {
+            ui.label("Click where the graph goes");
+            if ui.button("Cancel").clicked() {
+                self.placing = false;
+            }
+        } else if ui.button("Insert").clicked() {
+            self.placing = true;
+        }
+    }
+}
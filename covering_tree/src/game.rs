@@ -0,0 +1,257 @@
+use graph::graph::Graph;
+use graph::highlight::Highlight;
+use graph::history::Revision;
+use graph::spanning_tree::SpanningTreeAlgorithm;
+use graph::union_find::UnionFind;
+use std::collections::{HashMap, HashSet};
+
+/// Result of the last check of the paved roads
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Verdict {
+    /// Some houses cannot be reached with the paved roads
+    NotConnected {
+        components: usize,
+    },
+    Connected {
+        cost: f32,
+    },
+    Optimal {
+        cost: f32,
+    },
+}
+
+/// Muddy City game: pave enough roads to connect every house for the smallest cost
+#[derive(Debug, Clone, Default)]
+pub struct Game {
+    active: bool,
+    level: String,
+    /// Paved roads as `(smallest node id, biggest node id)`
+    paved: HashSet<(usize, usize)>,
+    attempts: u32,
+    start_time: f64,
+    /// Time taken to find the optimal solution
+    solved_time: Option<f64>,
+    reveal: bool,
+    verdict: Option<Verdict>,
+    /// Best cost of a connected selection for every level played
+    best_scores: HashMap<String, f32>,
+    optimal: Option<Optimal>,
+}
+
+/// Minimum covering tree of the town, computed again only when the graph changes
+#[derive(Debug, Clone)]
+struct Optimal {
+    revision: Revision,
+    cost: f32,
+    roads: HashSet<(usize, usize)>,
+}
+
+fn road(id1: usize, id2: usize) -> (usize, usize) {
+    (id1.min(id2), id1.max(id2))
+}
+
+impl Game {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn start(&mut self, level: String, time: f64) {
+        self.active = true;
+        self.level = level;
+        self.paved.clear();
+        self.attempts = 0;
+        self.start_time = time;
+        self.solved_time = None;
+        self.reveal = false;
+        self.verdict = None;
+    }
+
+    pub fn stop(&mut self, graph: &mut Graph) {
+        self.active = false;
+        graph.clear_highlights();
+    }
+
+    /// Pave the road between two houses or remove its pavement
+    pub fn toggle_road(&mut self, id1: usize, id2: usize) {
+        if !self.paved.remove(&road(id1, id2)) {
+            self.paved.insert(road(id1, id2));
+        }
+        self.verdict = None;
+    }
+
+    /// Total weight of the paved roads
+    pub fn cost(&self, graph: &Graph) -> f32 {
+        self.paved
+            .iter()
+            .filter_map(|&(id1, id2)| graph.weight(id1, id2))
+            .sum()
+    }
+
+    /// Minimum covering tree of the town
+    fn optimal(&mut self, graph: &Graph) -> &Optimal {
+        let revision = graph.revision();
+        if self.optimal.as_ref().map(|optimal| optimal.revision) != Some(revision) {
+            let tree = graph.covering_tree(SpanningTreeAlgorithm::Kruskal, false);
+            self.optimal = Some(Optimal {
+                revision,
+                cost: tree.total_weight(),
+                roads: tree
+                    .weighted_edges()
+                    .into_iter()
+                    .map(|(id1, id2, _)| road(id1, id2))
+                    .collect(),
+            });
+        }
+        self.optimal.as_ref().unwrap()
+    }
+
+    /// Number of groups of houses connected by the paved roads
+    fn n_components(&self, graph: &Graph) -> usize {
+        let mut union_find = UnionFind::new(graph.n_nodes());
+        for &(id1, id2) in self.paved.iter() {
+            if graph.weight(id1, id2).is_some() {
+                // the union find counts the nodes by their index
+                if let (Some(index1), Some(index2)) = (graph.node_index(id1), graph.node_index(id2))
+                {
+                    union_find.union(index1, index2);
+                }
+            }
+        }
+        union_find.count()
+    }
+
+    pub fn check(&mut self, graph: &Graph, time: f64) {
+        self.attempts += 1;
+        let components = self.n_components(graph);
+        // the houses that cannot be reached by any road are not asked to be connected
+        let verdict = if components > graph.components().count() {
+            Verdict::NotConnected { components }
+        } else {
+            let cost = self.cost(graph);
+            let best = self.best_scores.entry(self.level.clone()).or_insert(cost);
+            *best = best.min(cost);
+            // weights are floats: a small tolerance for the comparison with the optimal
+            if cost <= self.optimal(graph).cost + 1e-3 {
+                self.solved_time.get_or_insert(time - self.start_time);
+                Verdict::Optimal { cost }
+            } else {
+                Verdict::Connected { cost }
+            }
+        };
+        self.verdict = Some(verdict);
+    }
+
+    /// Colour the paved roads, and compare them to the optimal solution if it is revealed
+    pub fn update(&mut self, graph: &mut Graph) {
+        if !self.active {
+            return;
+        }
+        // the paved roads that do not exist anymore are forgotten
+        self.paved
+            .retain(|&(id1, id2)| graph.weight(id1, id2).is_some());
+
+        graph.clear_highlights();
+        if self.reveal {
+            self.optimal(graph);
+        }
+        let no_roads = HashSet::new();
+        let optimal = match &self.optimal {
+            Some(optimal) if self.reveal => &optimal.roads,
+            _ => &no_roads,
+        };
+
+        for (index, (id1, id2, _)) in graph.weighted_edges().into_iter().enumerate() {
+            let road = road(id1, id2);
+            let highlight = match (self.paved.contains(&road), self.reveal) {
+                (true, false) => Highlight::Accepted,
+                (false, false) => continue,
+                // compared to the optimal solution
+                (true, true) if optimal.contains(&road) => Highlight::Accepted,
+                (true, true) => Highlight::Rejected,
+                (false, true) if optimal.contains(&road) => Highlight::Considered,
+                (false, true) => continue,
+            };
+            graph.set_highlight_vertex(index, highlight);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, graph: &mut Graph, default_level: &str) {
+        let time = ui.input(|i| i.time);
+        if !self.active {
+            if ui.button("Start the game").clicked() {
+                self.start(default_level.to_string(), time);
+            }
+            return;
+        }
+        if ui.button("Stop the game").clicked() {
+            self.stop(graph);
+            return;
+        }
+
+        ui.label(format!("Level: {}", self.level));
+        let town = graph.components();
+        if !town.is_connected() {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!(
+                    "The town is split in {} groups that no road connects, \
+                     connect every group on its own",
+                    town.count()
+                ),
+            );
+        }
+        ui.label("Left click on the roads to pave them");
+        ui.label(format!(
+            "Paved roads: {} for a cost of {:.1}",
+            self.paved.len(),
+            self.cost(graph)
+        ));
+
+        ui.horizontal(|ui| {
+            if ui.button("Check").clicked() {
+                self.check(graph, time);
+            }
+            if ui.button("Clear roads").clicked() {
+                self.paved.clear();
+                self.verdict = None;
+            }
+        });
+        match self.verdict {
+            Some(Verdict::NotConnected { components }) => {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!("The houses are split in {components} groups"),
+                );
+            }
+            Some(Verdict::Connected { cost }) => {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "Every house is connected for {cost:.1}, the best is {:.1}",
+                        self.optimal(graph).cost
+                    ),
+                );
+            }
+            Some(Verdict::Optimal { cost }) => {
+                ui.colored_label(
+                    egui::Color32::GREEN,
+                    format!("Optimal! Every house is connected for {cost:.1}"),
+                );
+            }
+            None => {}
+        }
+
+        ui.label(format!("Attempts: {}", self.attempts));
+        let elapsed = self.solved_time.unwrap_or(time - self.start_time);
+        ui.label(format!("Time: {:.0} s", elapsed));
+        if let Some(best) = self.best_scores.get(&self.level) {
+            ui.label(format!("Best score: {best:.1}"));
+        }
+        ui.checkbox(&mut self.reveal, "Reveal the optimal solution");
+
+        if self.solved_time.is_none() {
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_secs(1));
+        }
+    }
+}
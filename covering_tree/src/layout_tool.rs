@@ -0,0 +1,215 @@
+use graph::camera::Camera;
+use graph::graph::Graph;
+use graph::history::Revision;
+use graph::layout::Layout;
+
+/// Nodes moving from their positions to the ones of a layout
+#[derive(Debug, Clone)]
+struct Animation {
+    start: Vec<(usize, egui::Pos2)>,
+    target: Vec<(usize, egui::Pos2)>,
+    start_time: f64,
+}
+
+/// What the tree view is laid out from, it is laid out again only when this changes
+#[derive(Debug, Clone, PartialEq)]
+struct TreeViewKey {
+    revision: Revision,
+    root: Option<usize>,
+    spacing: f32,
+}
+
+/// Place the nodes of the graph with a layout, at once or over some frames,
+/// and show the covering tree as a rooted tree in its own window
+#[derive(Debug, Clone)]
+pub struct LayoutTool {
+    layout: Layout,
+    /// Distance between the joined nodes
+    spacing: f32,
+    animate: bool,
+    /// Duration of the animation in seconds
+    duration: f64,
+    animation: Option<Animation>,
+    /// Why the layout could not be applied
+    error: Option<String>,
+    /// Window with the covering tree laid out as a tree
+    show_tree_view: bool,
+    /// Copy of the covering tree laid out as a tree, with what it was laid out from
+    tree_view: Option<(TreeViewKey, Result<Graph, String>)>,
+}
+
+impl Default for LayoutTool {
+    fn default() -> Self {
+        Self {
+            layout: Layout::default(),
+            spacing: 120.,
+            animate: true,
+            duration: 1.,
+            animation: None,
+            error: None,
+            show_tree_view: false,
+            tree_view: None,
+        }
+    }
+}
+
+/// Starts and ends slowly
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3. - 2. * t)
+}
+
+/// Copy of the covering tree laid out as a tree from a root, with its own camera
+fn rooted_tree(covering_tree: &Graph, root: Option<usize>, spacing: f32) -> Result<Graph, String> {
+    let root = root.ok_or_else(|| "the covering tree has no node".to_string())?;
+    let positions = covering_tree.layout_positions(Layout::Tree { root }, spacing)?;
+    let mut tree = covering_tree.clone();
+    tree.set_camera(Camera::default()).clear_highlights();
+    tree.preview_node_positions(&positions);
+    Ok(tree)
+}
+
+impl LayoutTool {
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Move the nodes of the animation, the whole move is recorded as one edit at the end
+    pub fn update(&mut self, ctx: &egui::Context, graph: &mut Graph) {
+        let Some(animation) = &self.animation else {
+            return;
+        };
+        let t = (ctx.input(|i| i.time) - animation.start_time) / self.duration;
+        if t >= 1. {
+            graph.preview_node_positions(&animation.start);
+            graph.set_node_positions(&animation.target);
+            self.animation = None;
+            return;
+        }
+        let t = smoothstep(t.max(0.) as f32);
+        let positions: Vec<(usize, egui::Pos2)> = animation
+            .start
+            .iter()
+            .zip(animation.target.iter())
+            .map(|(&(id, start), &(_, target))| (id, start + (target - start) * t))
+            .collect();
+        graph.preview_node_positions(&positions);
+        ctx.request_repaint();
+    }
+
+    /// Put the nodes back where they were before the animation
+    pub fn stop(&mut self, graph: &mut Graph) {
+        if let Some(animation) = self.animation.take() {
+            graph.preview_node_positions(&animation.start);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, graph: &mut Graph) {
+        egui::ComboBox::from_label("Layout")
+            .selected_text(format!("{}", self.layout))
+            .show_ui(ui, |ui| {
+                for candidate in Layout::ALL {
+                    if ui
+                        .selectable_label(self.layout.same_kind(&candidate), format!("{candidate}"))
+                        .clicked()
+                        && !self.layout.same_kind(&candidate)
+                    {
+                        self.layout = candidate;
+                        self.error = None;
+                    }
+                }
+            });
+        if let Layout::Tree { root } = &mut self.layout {
+            ui.horizontal(|ui| {
+                ui.label("Root");
+                let mut changed = ui.add(egui::DragValue::new(root)).changed();
+                if let Some(&selected) = graph.selected_nodes().first() {
+                    if ui.button("Selected node").clicked() {
+                        *root = selected;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    self.error = None;
+                }
+            });
+        }
+        ui.add(egui::Slider::new(&mut self.spacing, 40.0..=300.0).text("Spacing"));
+        ui.checkbox(&mut self.animate, "Animate");
+
+        if self.is_animating() {
+            if ui.button("Stop").clicked() {
+                self.stop(graph);
+            }
+        } else if ui.button("Apply the layout").clicked() {
+            let result = if self.animate {
+                graph
+                    .layout_positions(self.layout, self.spacing)
+                    .map(|target| {
+                        self.animation = Some(Animation {
+                            start: graph.node_positions(),
+                            target,
+                            start_time: ui.input(|i| i.time),
+                        });
+                    })
+            } else {
+                graph.apply_layout(self.layout, self.spacing)
+            };
+            self.error = result.err();
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        ui.checkbox(
+            &mut self.show_tree_view,
+            "Show the covering tree as a rooted tree",
+        );
+    }
+
+    /// Window with a copy of the covering tree laid out as a tree from the root of the layout,
+    /// or from its first node, laid out again only when the covering tree changes
+    pub fn tree_view(&mut self, ctx: &egui::Context, covering_tree: &Graph) {
+        if !self.show_tree_view {
+            return;
+        }
+        let key = TreeViewKey {
+            revision: covering_tree.revision(),
+            root: match self.layout {
+                Layout::Tree { root } => Some(root),
+                _ => covering_tree.model().nodes().first().map(|node| node.id),
+            },
+            spacing: self.spacing,
+        };
+        if self.tree_view.as_ref().map(|(laid_out, _)| laid_out) != Some(&key) {
+            let tree = rooted_tree(covering_tree, key.root, key.spacing);
+            self.tree_view = Some((key, tree));
+        }
+        let Some((_, tree)) = &mut self.tree_view else {
+            return;
+        };
+
+        let mut open = self.show_tree_view;
+        egui::Window::new("Rooted covering tree")
+            .open(&mut open)
+            .default_size(egui::vec2(400., 300.))
+            .show(ctx, |ui| match tree {
+                Ok(tree) => {
+                    // the view has its own camera, fitted to the window
+                    let (rect, _) = ui.allocate_exact_size(
+                        ui.available_size().max(egui::vec2(200., 150.)),
+                        egui::Sense::hover(),
+                    );
+                    tree.fit_to_view(rect);
+                    tree.draw(&mut ui.child_ui(rect, *ui.layout()));
+                }
+                Err(error) => {
+                    ui.colored_label(egui::Color32::RED, error.as_str());
+                }
+            });
+        self.show_tree_view = open;
+    }
+
+    pub fn is_tree_view_open(&self) -> bool {
+        self.show_tree_view
+    }
+}
@@ -0,0 +1,94 @@
+use graph::level::Level;
+
+/// Levels shipped with the app
+const BUILTIN_LEVELS: [&str; 4] = [
+    include_str!("../../data/levels/1_village.level"),
+    include_str!("../../data/levels/2_muddy_city.level"),
+    include_str!("../../data/levels/3_market_town.level"),
+    include_str!("../../data/levels/4_big_city.level"),
+];
+
+/// Window to choose the town of the Muddy City game
+#[derive(Debug, Clone)]
+pub struct LevelSelect {
+    levels: Vec<Level>,
+    /// Directory with the levels added by the teachers
+    directory: String,
+    errors: Vec<String>,
+    pub open: bool,
+}
+
+impl Default for LevelSelect {
+    fn default() -> Self {
+        Self {
+            levels: BUILTIN_LEVELS
+                .iter()
+                .map(|text| Level::parse(text).expect("builtin levels are valid"))
+                .collect(),
+            directory: String::from("levels"),
+            errors: Vec::new(),
+            open: false,
+        }
+    }
+}
+
+impl LevelSelect {
+    /// Add the levels of `self.directory` after the builtin ones
+    pub fn load_directory(&mut self) {
+        self.errors.clear();
+        match Level::load_dir(&self.directory) {
+            Ok(levels) => {
+                for level in levels {
+                    match level {
+                        Ok(level) if !self.levels.contains(&level) => self.levels.push(level),
+                        Ok(_) => {}
+                        Err((file, error)) => self.errors.push(format!("{file}: {error}")),
+                    }
+                }
+            }
+            Err(error) => self.errors.push(format!("{}: {error}", self.directory)),
+        }
+    }
+
+    /// Returns the level chosen by the user
+    pub fn ui(&mut self, ctx: &egui::Context) -> Option<Level> {
+        let mut chosen = None;
+        let mut open = self.open;
+        egui::Window::new("Levels").open(&mut open).show(ctx, |ui| {
+            egui::Grid::new("levels_grid").striped(true).show(ui, |ui| {
+                ui.strong("Town");
+                ui.strong("Difficulty");
+                ui.strong("Houses");
+                ui.strong("Roads");
+                ui.end_row();
+                for level in self.levels.iter() {
+                    ui.label(&level.name);
+                    ui.label("★".repeat(level.difficulty as usize));
+                    ui.label(format!("{}", level.houses.len()));
+                    ui.label(format!("{}", level.roads.len()));
+                    if ui.button("Play").clicked() {
+                        chosen = Some(level.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+
+            // there is no file system in the browser
+            if cfg!(not(target_arch = "wasm32")) {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Directory");
+                    ui.text_edit_singleline(&mut self.directory);
+                    if ui.button("Load").clicked() {
+                        self.load_directory();
+                    }
+                });
+                for error in self.errors.iter() {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            }
+        });
+        self.open = open && chosen.is_none();
+        chosen
+    }
+}
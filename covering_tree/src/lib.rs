@@ -1,2 +1,9 @@
 #![warn(clippy::all, rust_2018_idioms)]
-pub mod app;
\ No newline at end of file
+pub mod app;
+mod family_menu;
+mod game;
+mod layout_tool;
+mod level_select;
+mod path_finder;
+mod playback;
+mod town_generator;
@@ -0,0 +1,180 @@
+use graph::graph::Graph;
+use graph::highlight::Highlight;
+use graph::history::Revision;
+use graph::shortest_path::{Path, ShortestPathAlgorithm};
+
+/// Shortest path between two houses picked on the graph,
+/// compared with the path between them inside the covering tree
+#[derive(Debug, Clone, Default)]
+pub struct PathFinder {
+    active: bool,
+    algorithm: ShortestPathAlgorithm,
+    source: Option<usize>,
+    destination: Option<usize>,
+    path: Option<Path>,
+    tree_path: Option<Path>,
+    /// What the paths were found from, they are found again only when this changes
+    key: Option<PathKey>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PathKey {
+    revision: Revision,
+    tree_revision: Revision,
+    algorithm: ShortestPathAlgorithm,
+    source: usize,
+    destination: usize,
+}
+
+fn path_label(path: &Path) -> String {
+    let nodes: Vec<String> = path.nodes.iter().map(|id| id.to_string()).collect();
+    nodes.join(" → ")
+}
+
+impl PathFinder {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn start(&mut self) {
+        *self = Self {
+            active: true,
+            algorithm: self.algorithm,
+            ..Default::default()
+        };
+    }
+
+    pub fn stop(&mut self, graph: &mut Graph, covering_tree: &mut Graph) {
+        *self = Self {
+            algorithm: self.algorithm,
+            ..Default::default()
+        };
+        graph.clear_highlights();
+        covering_tree.clear_highlights();
+    }
+
+    /// The first node clicked is the source, the second one the destination,
+    /// the next click starts a new path
+    pub fn pick(&mut self, id: usize) {
+        match (self.source, self.destination) {
+            (Some(source), None) if source != id => self.destination = Some(id),
+            _ => {
+                self.source = Some(id);
+                self.destination = None;
+            }
+        }
+    }
+
+    /// Find the paths and highlight them on the graph and on its covering tree
+    pub fn update(&mut self, graph: &mut Graph, covering_tree: &mut Graph) {
+        if !self.active {
+            return;
+        }
+        // the picked nodes may have been removed
+        if self
+            .source
+            .map_or(false, |id| graph.node_index(id).is_none())
+        {
+            self.source = None;
+        }
+        if self
+            .destination
+            .map_or(false, |id| graph.node_index(id).is_none())
+        {
+            self.destination = None;
+        }
+
+        let key = match (self.source, self.destination) {
+            (Some(source), Some(destination)) => Some(PathKey {
+                revision: graph.revision(),
+                tree_revision: covering_tree.revision(),
+                algorithm: self.algorithm,
+                source,
+                destination,
+            }),
+            _ => None,
+        };
+        if key != self.key {
+            (self.path, self.tree_path) = match &key {
+                Some(key) => (
+                    graph.shortest_path(key.algorithm, key.source, key.destination),
+                    covering_tree.shortest_path(key.algorithm, key.source, key.destination),
+                ),
+                None => (None, None),
+            };
+            self.key = key;
+        }
+
+        graph.clear_highlights();
+        covering_tree.clear_highlights();
+        for id in [self.source, self.destination].into_iter().flatten() {
+            graph.set_highlight_node(id, Highlight::Path);
+        }
+        for (graph, path) in [(graph, &self.path), (covering_tree, &self.tree_path)] {
+            for &e in path.iter().flat_map(|path| path.edges.iter()) {
+                graph.set_highlight_vertex(e, Highlight::Path);
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, graph: &mut Graph, covering_tree: &mut Graph) {
+        egui::ComboBox::from_label("Path algorithm")
+            .selected_text(format!("{}", self.algorithm))
+            .show_ui(ui, |ui| {
+                for algorithm in ShortestPathAlgorithm::ALL {
+                    ui.selectable_value(&mut self.algorithm, algorithm, format!("{algorithm}"));
+                }
+            });
+        if !self.active {
+            if ui.button("Pick two houses").clicked() {
+                self.start();
+            }
+            return;
+        }
+        if ui.button("Stop").clicked() {
+            self.stop(graph, covering_tree);
+            return;
+        }
+
+        let (Some(source), Some(destination)) = (self.source, self.destination) else {
+            match self.source {
+                None => ui.label("Click on the first house"),
+                Some(source) => ui.label(format!("From {source}, click on the second house")),
+            };
+            return;
+        };
+        ui.label(format!("From {source} to {destination}"));
+        match &self.path {
+            Some(path) => {
+                ui.label(path_label(path));
+                ui.label(format!(
+                    "Length: {:.1} ({} nodes explored)",
+                    path.length, path.expanded
+                ));
+            }
+            None => {
+                ui.label("These houses are not connected");
+            }
+        }
+        match (&self.path, &self.tree_path) {
+            // there is no percentage of a free path
+            (Some(path), Some(tree_path)) if path.length == 0. => {
+                ui.label(format!(
+                    "Length in the covering tree: {:.1}",
+                    tree_path.length
+                ));
+            }
+            (Some(path), Some(tree_path)) => {
+                ui.label(format!(
+                    "Length in the covering tree: {:.1} ({:+.0}%)",
+                    tree_path.length,
+                    (tree_path.length / path.length - 1.) * 100.
+                ));
+            }
+            (Some(_), None) => {
+                ui.label("Not connected in the covering tree");
+            }
+            (None, _) => {}
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use graph::graph::Graph;
+use graph::highlight::Highlight;
+use graph::history::Revision;
+use graph::spanning_tree::{n_components, SpanningTreeAlgorithm, Step, StepKind};
+
+/// Step by step replay of a spanning tree algorithm on the vertices of a graph
+#[derive(Debug, Clone)]
+pub struct Playback {
+    steps: Vec<Step>,
+    /// Number of nodes of the graph when the steps were recorded
+    n_nodes: usize,
+    /// Number of steps already played
+    current: usize,
+    playing: bool,
+    /// Number of steps played every second
+    speed: f64,
+    last_step_time: f64,
+    /// Revision of the graph when the steps were recorded
+    revision: Revision,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            steps: Vec::new(),
+            n_nodes: 0,
+            current: 0,
+            playing: false,
+            speed: 2.0,
+            last_step_time: 0.0,
+            revision: Revision::default(),
+        }
+    }
+}
+
+impl Playback {
+    pub fn is_active(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    pub fn record(&mut self, graph: &Graph, algorithm: SpanningTreeAlgorithm, max: bool) {
+        self.steps = graph.covering_tree_steps(algorithm, max);
+        self.n_nodes = graph.n_nodes();
+        self.current = 0;
+        self.playing = false;
+        self.revision = graph.revision();
+    }
+
+    pub fn stop(&mut self, graph: &mut Graph) {
+        *self = Self {
+            speed: self.speed,
+            ..Default::default()
+        };
+        graph.clear_highlights();
+    }
+
+    pub fn step_forward(&mut self) {
+        self.current = (self.current + 1).min(self.steps.len());
+    }
+
+    pub fn step_back(&mut self) {
+        self.current = self.current.saturating_sub(1);
+    }
+
+    /// Advance the playback with the time and colour the vertices of the graph
+    pub fn update(&mut self, ctx: &egui::Context, graph: &mut Graph) {
+        if !self.is_active() {
+            return;
+        }
+        // the graph was edited: the steps do not match its vertices anymore
+        if graph.revision() != self.revision {
+            self.stop(graph);
+            return;
+        }
+
+        let time = ctx.input(|i| i.time);
+        if self.playing {
+            if time - self.last_step_time >= 1.0 / self.speed {
+                self.step_forward();
+                self.last_step_time = time;
+            }
+            if self.current == self.steps.len() {
+                self.playing = false;
+            }
+            ctx.request_repaint();
+        }
+
+        graph.clear_highlights();
+        for (i, step) in self.steps[..self.current].iter().enumerate() {
+            let highlight = match step.kind {
+                StepKind::Accepted => Highlight::Accepted,
+                StepKind::Rejected => Highlight::Rejected,
+                // only the edge currently looked at is shown as considered
+                StepKind::Considered if i + 1 == self.current => Highlight::Considered,
+                StepKind::Considered => continue,
+            };
+            graph.set_highlight_vertex(step.edge, highlight);
+        }
+    }
+
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        graph: &mut Graph,
+        algorithm: SpanningTreeAlgorithm,
+        max: bool,
+    ) {
+        ui.horizontal(|ui| {
+            if ui.button("Record steps").clicked() {
+                self.record(graph, algorithm, max);
+            }
+            if self.is_active() && ui.button("Stop").clicked() {
+                self.stop(graph);
+            }
+        });
+        if !self.is_active() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("⏮").clicked() {
+                self.playing = false;
+                self.current = 0;
+            }
+            if ui.button("⏪").clicked() {
+                self.playing = false;
+                self.step_back();
+            }
+            let play_pause = if self.playing { "⏸" } else { "▶" };
+            if ui.button(play_pause).clicked() {
+                self.playing = !self.playing;
+                if self.current == self.steps.len() {
+                    self.current = 0;
+                }
+            }
+            if ui.button("⏩").clicked() {
+                self.playing = false;
+                self.step_forward();
+            }
+            if ui.button("⏭").clicked() {
+                self.playing = false;
+                self.current = self.steps.len();
+            }
+        });
+        ui.add(egui::Slider::new(&mut self.speed, 0.5..=20.0).text("Steps per second"));
+
+        ui.label(format!("Step {} / {}", self.current, self.steps.len()));
+        if let Some(step) = self.current.checked_sub(1).map(|i| &self.steps[i]) {
+            if let Some((id1, id2, weight)) = graph.weighted_edges().get(step.edge) {
+                ui.label(format!("Vertex ({id1}, {id2}) of weight {weight:.1}"));
+            }
+            ui.label(format!("{}", step.kind));
+            let n_components = n_components(self.n_nodes, &self.steps[..self.current]);
+            ui.label(format!("Components: {n_components}"));
+        }
+    }
+}
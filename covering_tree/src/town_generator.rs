@@ -0,0 +1,114 @@
+use graph::generator::{Placement, TownGenerator};
+use graph::level::Level;
+use graph::proximity::ProximityGraph;
+
+/// What to do with a generated town
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TownUse {
+    /// Start the Muddy City game in the town
+    Play,
+    /// Replace the graph by the town
+    Edit,
+}
+
+/// Window to generate a random town from a seed
+#[derive(Debug, Clone, Default)]
+pub struct TownDialog {
+    generator: TownGenerator,
+    pub open: bool,
+}
+
+impl TownDialog {
+    /// Returns the town generated by the user, with what to do with it
+    pub fn ui(&mut self, ctx: &egui::Context) -> Option<(Level, TownUse)> {
+        let mut chosen = None;
+        let mut open = self.open;
+        let generator = &mut self.generator;
+        egui::Window::new("Random town")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Seed");
+                    ui.add(egui::DragValue::new(&mut generator.seed));
+                    if ui
+                        .button("Next")
+                        .on_hover_text("The same seed always gives the same town")
+                        .clicked()
+                    {
+                        generator.seed = generator.seed.wrapping_add(1);
+                    }
+                });
+                ui.add(egui::Slider::new(&mut generator.houses, 2..=100).text("Houses"));
+
+                egui::ComboBox::from_label("Placement")
+                    .selected_text(format!("{}", generator.placement))
+                    .show_ui(ui, |ui| {
+                        for candidate in Placement::ALL {
+                            if ui
+                                .selectable_label(
+                                    generator.placement.same_kind(&candidate),
+                                    format!("{candidate}"),
+                                )
+                                .clicked()
+                                && !generator.placement.same_kind(&candidate)
+                            {
+                                generator.placement = candidate;
+                            }
+                        }
+                    });
+                match &mut generator.placement {
+                    Placement::Clustered { clusters } => {
+                        ui.add(egui::Slider::new(clusters, 1..=10).text("Villages"));
+                    }
+                    Placement::JitteredGrid { jitter } => {
+                        ui.add(egui::Slider::new(jitter, 0.0..=1.0).text("Jitter"));
+                    }
+                    _ => {}
+                }
+
+                egui::ComboBox::from_label("Candidate roads")
+                    .selected_text(format!("{}", generator.roads))
+                    .show_ui(ui, |ui| {
+                        for candidate in ProximityGraph::ALL {
+                            if ui
+                                .selectable_label(
+                                    generator.roads.same_kind(&candidate),
+                                    format!("{candidate}"),
+                                )
+                                .clicked()
+                                && !generator.roads.same_kind(&candidate)
+                            {
+                                generator.roads = candidate;
+                            }
+                        }
+                    });
+                match &mut generator.roads {
+                    ProximityGraph::KNearest { k } => {
+                        ui.add(egui::Slider::new(k, 1..=10).text("Neighbours"));
+                    }
+                    ProximityGraph::UnitDisk { radius } => {
+                        ui.add(egui::Slider::new(radius, 10.0..=1000.0).text("Radius"));
+                    }
+                    _ => {}
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Paving costs from");
+                    ui.add(egui::DragValue::new(&mut generator.min_cost).clamp_range(1..=99));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(&mut generator.max_cost).clamp_range(1..=99));
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Play").clicked() {
+                        chosen = Some((generator.generate(), TownUse::Play));
+                    }
+                    if ui.button("Edit").clicked() {
+                        chosen = Some((generator.generate(), TownUse::Edit));
+                    }
+                });
+            });
+        self.open = open && chosen.is_none();
+        chosen
+    }
+}
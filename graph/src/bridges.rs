@@ -0,0 +1,179 @@
+use crate::spanning_tree::WeightedEdge;
+
+/// Edges and nodes whose removal splits their connected component
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CriticalParts {
+    /// Indices of the bridges, in the edges given to `critical_parts`
+    pub bridges: Vec<usize>,
+    /// Articulation points, sorted
+    pub articulation_points: Vec<usize>,
+}
+
+/// Bridges and articulation points of the graph with `n_nodes` nodes,
+/// with Tarjan's depth first search and its lowest reachable discovery times
+pub fn critical_parts(n_nodes: usize, edges: &[WeightedEdge]) -> CriticalParts {
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); n_nodes];
+    for (e, &(id1, id2, _)) in edges.iter().enumerate() {
+        incident[id1].push(e);
+        incident[id2].push(e);
+    }
+    let other = |e: usize, node: usize| {
+        let (id1, id2, _) = edges[e];
+        if id1 == node {
+            id2
+        } else {
+            id1
+        }
+    };
+
+    // order in which the nodes are discovered, and the smallest one reachable
+    // from their subtree with at most one edge going back up
+    let mut discovery: Vec<Option<usize>> = vec![None; n_nodes];
+    let mut low = vec![0; n_nodes];
+    let mut time = 0;
+    let mut parts = CriticalParts::default();
+    let mut is_articulation = vec![false; n_nodes];
+
+    for root in 0..n_nodes {
+        if discovery[root].is_some() {
+            continue;
+        }
+        discovery[root] = Some(time);
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // the recursion is a stack of (node, edge to its parent, next incident edge)
+        let mut stack: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some((node, parent_edge, next)) = stack.last_mut() {
+            let node = *node;
+            if let Some(&e) = incident[node].get(*next) {
+                *next += 1;
+                // going back through the same edge is not a cycle (parallel edges are)
+                if Some(e) == *parent_edge {
+                    continue;
+                }
+                let adj = other(e, node);
+                match discovery[adj] {
+                    Some(adj_discovery) => low[node] = low[node].min(adj_discovery),
+                    None => {
+                        discovery[adj] = Some(time);
+                        low[adj] = time;
+                        time += 1;
+                        stack.push((adj, Some(e), 0));
+                    }
+                }
+                continue;
+            }
+
+            // every neighbour of the node was explored
+            let parent_edge = *parent_edge;
+            stack.pop();
+            let (Some(&(parent, _, _)), Some(e)) = (stack.last(), parent_edge) else {
+                continue;
+            };
+            low[parent] = low[parent].min(low[node]);
+            let parent_discovery = discovery[parent].unwrap_or(0);
+            if low[node] > parent_discovery {
+                parts.bridges.push(e);
+            }
+            if parent == root {
+                root_children += 1;
+            } else if low[node] >= parent_discovery {
+                is_articulation[parent] = true;
+            }
+        }
+        if root_children >= 2 {
+            is_articulation[root] = true;
+        }
+    }
+
+    parts.bridges.sort_unstable();
+    parts.articulation_points = (0..n_nodes).filter(|&x| is_articulation[x]).collect();
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Components;
+    use crate::generator::test_inputs::random_edges;
+    use crate::generator::Rng;
+
+    fn count_components(n_nodes: usize, edges: &[WeightedEdge]) -> usize {
+        Components::new(n_nodes, edges).count()
+    }
+
+    /// An edge is a bridge if the graph without it has more components
+    fn brute_force_bridges(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+        let count = count_components(n_nodes, edges);
+        (0..edges.len())
+            .filter(|&e| {
+                let mut without = edges.to_vec();
+                without.remove(e);
+                count_components(n_nodes, &without) > count
+            })
+            .collect()
+    }
+
+    /// A node is an articulation point if the graph without it has more components,
+    /// it is left alone without its edges so it is counted once more
+    fn brute_force_articulation_points(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+        let count = count_components(n_nodes, edges);
+        (0..n_nodes)
+            .filter(|&x| {
+                let without: Vec<WeightedEdge> = edges
+                    .iter()
+                    .copied()
+                    .filter(|&(id1, id2, _)| id1 != x && id2 != x)
+                    .collect();
+                count_components(n_nodes, &without) - 1 > count
+            })
+            .collect()
+    }
+
+    fn assert_brute_force(n_nodes: usize, edges: &[WeightedEdge]) {
+        let parts = critical_parts(n_nodes, edges);
+        assert_eq!(
+            parts.bridges,
+            brute_force_bridges(n_nodes, edges),
+            "{edges:?}"
+        );
+        assert_eq!(
+            parts.articulation_points,
+            brute_force_articulation_points(n_nodes, edges),
+            "{edges:?}"
+        );
+    }
+
+    #[test]
+    fn same_as_brute_force_on_random_graphs() {
+        let mut rng = Rng::new(5);
+        for _ in 0..300 {
+            let n_nodes = 1 + rng.index(12);
+            // sparse graphs have several components, dense ones have parallel edges
+            let n_edges = rng.index(2 * n_nodes);
+            assert_brute_force(n_nodes, &random_edges(&mut rng, n_nodes, n_edges, false));
+        }
+    }
+
+    #[test]
+    fn known_graphs() {
+        // two triangles joined by the bridge 2-3, and an isolated node 6
+        let edges = [
+            (0, 1, 0.),
+            (1, 2, 0.),
+            (2, 0, 0.),
+            (2, 3, 0.),
+            (3, 4, 0.),
+            (4, 5, 0.),
+            (5, 3, 0.),
+        ];
+        let parts = critical_parts(7, &edges);
+        assert_eq!(parts.bridges, vec![3]);
+        assert_eq!(parts.articulation_points, vec![2, 3]);
+        // a parallel edge is not a bridge
+        let parts = critical_parts(2, &[(0, 1, 0.), (1, 0, 0.)]);
+        assert!(parts.bridges.is_empty() && parts.articulation_points.is_empty());
+        assert_eq!(critical_parts(0, &[]), CriticalParts::default());
+    }
+}
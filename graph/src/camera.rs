@@ -0,0 +1,153 @@
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 10.0;
+/// Space left around the graph when it is fitted to the view, in pixels
+const FIT_MARGIN: f32 = 50.0;
+
+/// Transform between the positions of the graph (world) and the screen
+///
+/// The camera is moved with the mouse wheel to zoom around the cursor,
+/// and with the middle button or space and the left button to pan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Position on the screen of the origin of the world
+    offset: egui::Vec2,
+    zoom: f32,
+    /// Last position of the pointer on the screen
+    pointer: Option<egui::Pos2>,
+    panning: bool,
+    space_pressed: bool,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            offset: egui::Vec2::ZERO,
+            zoom: 1.0,
+            pointer: None,
+            panning: false,
+            space_pressed: false,
+        }
+    }
+}
+
+impl Camera {
+    // GETTERS /////////////////////////////////////////////////////////////////////
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Last position of the pointer on the screen
+    pub fn pointer(&self) -> Option<egui::Pos2> {
+        self.pointer
+    }
+
+    pub fn is_panning(&self) -> bool {
+        self.panning
+    }
+
+    pub fn to_screen(&self, pos: egui::Pos2) -> egui::Pos2 {
+        (pos.to_vec2() * self.zoom + self.offset).to_pos2()
+    }
+
+    pub fn to_world(&self, pos: egui::Pos2) -> egui::Pos2 {
+        ((pos.to_vec2() - self.offset) / self.zoom).to_pos2()
+    }
+
+    /// The same event with its position in the world
+    pub fn event_to_world(&self, event: &egui::Event) -> egui::Event {
+        match event {
+            egui::Event::PointerMoved(pos) => egui::Event::PointerMoved(self.to_world(*pos)),
+            egui::Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers,
+            } => egui::Event::PointerButton {
+                pos: self.to_world(*pos),
+                button: *button,
+                pressed: *pressed,
+                modifiers: *modifiers,
+            },
+            event => event.clone(),
+        }
+    }
+
+    // SETTERS /////////////////////////////////////////////////////////////////////
+    pub fn pan(&mut self, delta: egui::Vec2) -> &mut Self {
+        self.offset += delta;
+        self
+    }
+
+    /// Zoom by `factor` keeping the point of the screen `center` at the same place
+    pub fn zoom_around(&mut self, center: egui::Pos2, factor: f32) -> &mut Self {
+        let world_center = self.to_world(center);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset = center.to_vec2() - world_center.to_vec2() * self.zoom;
+        self
+    }
+
+    /// Show the rectangle `world` as big as possible in the rectangle `screen`
+    pub fn fit(&mut self, world: egui::Rect, screen: egui::Rect) -> &mut Self {
+        let screen = screen.shrink(FIT_MARGIN);
+        // a single node or aligned nodes only constrain one direction
+        let zoom_x = screen.width() / world.width();
+        let zoom_y = screen.height() / world.height();
+        let zoom = zoom_x.min(zoom_y);
+        if zoom.is_finite() && zoom > 0. {
+            self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+        self.offset = screen.center().to_vec2() - world.center().to_vec2() * self.zoom;
+        self
+    }
+
+    // UPDATE //////////////////////////////////////////////////////////////////////
+    /// Move the camera with an event, returns true if the event was used by the camera
+    pub fn update(&mut self, event: &egui::Event) -> bool {
+        match event {
+            egui::Event::Scroll(delta) => {
+                if let Some(pointer) = self.pointer {
+                    self.zoom_around(pointer, (delta.y / 500.).exp());
+                }
+                true
+            }
+            egui::Event::Zoom(factor) => {
+                if let Some(pointer) = self.pointer {
+                    self.zoom_around(pointer, *factor);
+                }
+                true
+            }
+            egui::Event::Key {
+                key: egui::Key::Space,
+                pressed,
+                ..
+            } => {
+                self.space_pressed = *pressed;
+                true
+            }
+            egui::Event::PointerButton {
+                button: egui::PointerButton::Middle,
+                pressed,
+                ..
+            } => {
+                self.panning = *pressed;
+                true
+            }
+            egui::Event::PointerButton {
+                button: egui::PointerButton::Primary,
+                pressed,
+                ..
+            } if self.space_pressed || self.panning => {
+                self.panning = *pressed;
+                true
+            }
+            egui::Event::PointerMoved(pos) => {
+                if let (true, Some(pointer)) = (self.panning, self.pointer) {
+                    self.pan(*pos - pointer);
+                }
+                self.pointer = Some(*pos);
+                self.panning
+            }
+            _ => false,
+        }
+    }
+}
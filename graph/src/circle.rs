@@ -1,6 +1,6 @@
+/// Circle drawn around a center given when it is drawn
 #[derive(Debug, Clone, Copy)]
 pub struct Circle {
-    center: egui::Pos2,
     radius: f32,
     stroke: egui::Stroke,
 }
@@ -8,9 +8,8 @@ pub struct Circle {
 impl Circle {
     // CONSTRUCTOR /////////////////////////////////////////////////////////////////
     // add stroke in the parameters
-    pub fn new(center: egui::Pos2, stroke: egui::Stroke) -> Self {
+    pub fn new(stroke: egui::Stroke) -> Self {
         Self {
-            center,
             radius: 10.0,
             stroke,
         }
@@ -33,10 +32,6 @@ impl Circle {
         self.stroke.color = color;
     }
 
-    pub fn set_center(&mut self, center: egui::Pos2) {
-        self.center = center;
-    }
-
     /*
     |----------------|
     |                |
@@ -46,25 +41,22 @@ impl Circle {
     */
 
     // GETTERS //////////////////////////////////////////////////////////////////////
-    pub fn center(&self) -> egui::Pos2 {
-        self.center
+    pub fn radius(&self) -> f32 {
+        self.radius
     }
 
-    pub fn contains(&self, point: egui::Pos2) -> bool {
-        let delta = point - self.center;
-        delta.length() < self.radius
+    pub fn stroke(&self) -> egui::Stroke {
+        self.stroke
     }
 
-    pub fn follow_mouse(&mut self, event: &egui::Event) {
-        if let egui::Event::PointerMoved(pos) = event {
-            self.center = *pos;
-        }
+    pub fn contains(&self, center: egui::Pos2, point: egui::Pos2) -> bool {
+        let delta = point - center;
+        delta.length() < self.radius
     }
 
-    pub fn draw(&self, ui: &mut egui::Ui) {
-        ui.painter()
-            .circle_stroke(self.center, self.radius, self.stroke);
+    pub fn draw(&self, ui: &mut egui::Ui, center: egui::Pos2) {
+        ui.painter().circle_stroke(center, self.radius, self.stroke);
         ui.painter()
-            .circle_filled(self.center, self.radius, egui::Color32::WHITE);
+            .circle_filled(center, self.radius, egui::Color32::WHITE);
     }
 }
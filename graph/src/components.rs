@@ -0,0 +1,154 @@
+use crate::spanning_tree::WeightedEdge;
+use crate::union_find::UnionFind;
+
+/// Connected components of a graph, numbered from 0 in the order of their first node
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Components {
+    /// Component of every node, by the index of the node
+    labels: Vec<usize>,
+    /// Number of nodes of every component
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    /// Components of the graph with `n_nodes` nodes
+    pub fn new(n_nodes: usize, edges: &[WeightedEdge]) -> Self {
+        let mut union_find = UnionFind::new(n_nodes);
+        for &(id1, id2, _) in edges.iter() {
+            union_find.union(id1, id2);
+        }
+
+        // the roots of the union find are renumbered from 0
+        let mut numbers = vec![None; n_nodes];
+        let mut labels = Vec::with_capacity(n_nodes);
+        let mut sizes = Vec::new();
+        for x in 0..n_nodes {
+            let root = union_find.find(x);
+            let label = *numbers[root].get_or_insert_with(|| {
+                sizes.push(0);
+                sizes.len() - 1
+            });
+            sizes[label] += 1;
+            labels.push(label);
+        }
+        Self { labels, sizes }
+    }
+
+    // GETTERS /////////////////////////////////////////////////////////////////////
+    /// Number of components
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Checks if every node can be reached from every other one
+    pub fn is_connected(&self) -> bool {
+        self.count() <= 1
+    }
+
+    /// Component of every node, in the order of the nodes
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    /// Component of the node at `index`
+    pub fn label(&self, index: usize) -> usize {
+        self.labels[index]
+    }
+
+    /// Number of nodes of every component
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Indices of the nodes of a component
+    pub fn members(&self, label: usize) -> Vec<usize> {
+        (0..self.labels.len())
+            .filter(|&index| self.labels[index] == label)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::test_inputs::random_edges;
+    use crate::generator::Rng;
+
+    /// Nodes reached from every node, by adding the neighbours until nothing changes
+    fn reachable(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<Vec<bool>> {
+        let mut reached: Vec<Vec<bool>> = (0..n_nodes)
+            .map(|i| (0..n_nodes).map(|j| i == j).collect())
+            .collect();
+        for _ in 0..n_nodes {
+            for row in reached.iter_mut() {
+                for &(i, j, _) in edges {
+                    if row[i] || row[j] {
+                        (row[i], row[j]) = (true, true);
+                    }
+                }
+            }
+        }
+        reached
+    }
+
+    #[test]
+    fn isolated_nodes_are_alone() {
+        let components = Components::new(4, &[]);
+        assert_eq!(components.labels(), [0, 1, 2, 3]);
+        assert_eq!(components.sizes(), [1, 1, 1, 1]);
+        assert_eq!(components.count(), 4);
+        assert!(!components.is_connected());
+
+        // no node or a single one is connected
+        assert!(Components::new(0, &[]).is_connected());
+        assert!(Components::new(1, &[]).is_connected());
+    }
+
+    #[test]
+    fn several_components() {
+        // 0 - 2 - 4, 1 - 3 - 6 and 5 alone
+        let edges = [(0, 2, 1.), (4, 2, 1.), (1, 3, 1.), (6, 1, 1.)];
+        let components = Components::new(7, &edges);
+        assert_eq!(components.labels(), [0, 1, 0, 1, 0, 2, 1]);
+        assert_eq!(components.sizes(), [3, 3, 1]);
+        assert_eq!(components.count(), 3);
+        assert!(!components.is_connected());
+        assert_eq!(components.label(6), 1);
+        assert_eq!(components.members(0), [0, 2, 4]);
+        assert_eq!(components.members(1), [1, 3, 6]);
+        assert_eq!(components.members(2), [5]);
+
+        // 2 - 3 joins the first two
+        let edges = [&edges[..], &[(2, 3, 1.)]].concat();
+        let components = Components::new(7, &edges);
+        assert_eq!(components.labels(), [0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(components.sizes(), [6, 1]);
+    }
+
+    #[test]
+    fn same_label_when_reachable_on_random_graphs() {
+        let mut rng = Rng::new(14);
+        for n_nodes in 1..30 {
+            let connected = n_nodes % 3 == 0;
+            let edges = random_edges(&mut rng, n_nodes, n_nodes * 2 / 3, connected);
+            let components = Components::new(n_nodes, &edges);
+            let reached = reachable(n_nodes, &edges);
+            for (i, row) in reached.iter().enumerate() {
+                for (j, &reached) in row.iter().enumerate() {
+                    assert_eq!(components.label(i) == components.label(j), reached);
+                }
+                let size = row.iter().filter(|&&reached| reached).count();
+                assert_eq!(components.sizes()[components.label(i)], size);
+            }
+            // numbered in the order of their first node
+            let first_nodes: Vec<usize> = (0..components.count())
+                .map(|label| components.members(label)[0])
+                .collect();
+            assert!(first_nodes.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(
+                components.is_connected(),
+                connected || components.count() == 1
+            );
+        }
+    }
+}
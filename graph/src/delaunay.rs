@@ -0,0 +1,475 @@
+use crate::spanning_tree::kruskal;
+use std::collections::HashMap;
+
+/// Size of the triangle containing every point, relative to the size of the points
+const SUPER_TRIANGLE_SCALE: f64 = 1e4;
+
+/// Triangle of the triangulation with its circumcircle
+#[derive(Debug, Clone, Copy)]
+struct Triangle {
+    vertices: [usize; 3],
+    center: (f64, f64),
+    /// Square of the radius of the circumcircle
+    radius2: f64,
+}
+
+impl Triangle {
+    /// `None` if the three points are aligned
+    fn new(vertices: [usize; 3], points: &[(f64, f64)]) -> Option<Self> {
+        let [(ax, ay), (bx, by), (cx, cy)] = vertices.map(|v| points[v]);
+        let d = 2. * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+        if d == 0. {
+            return None;
+        }
+        let (a2, b2, c2) = (ax * ax + ay * ay, bx * bx + by * by, cx * cx + cy * cy);
+        let center = (
+            (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d,
+            (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d,
+        );
+        let radius2 = (ax - center.0).powi(2) + (ay - center.1).powi(2);
+        Some(Self {
+            vertices,
+            center,
+            radius2,
+        })
+    }
+
+    fn circumcircle_contains(&self, (x, y): (f64, f64)) -> bool {
+        (x - self.center.0).powi(2) + (y - self.center.1).powi(2) < self.radius2
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+fn edge_key((a, b): (usize, usize)) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// Indices of the points sorted by position without the duplicates,
+/// and the edges joining every duplicate to the point it repeats
+fn unique_points(points: &[(f32, f32)]) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut sorted: Vec<usize> = (0..points.len()).collect();
+    sorted.sort_by(|&i, &j| {
+        let ((xi, yi), (xj, yj)) = (points[i], points[j]);
+        xi.total_cmp(&xj).then(yi.total_cmp(&yj))
+    });
+    let mut unique: Vec<usize> = Vec::new();
+    let mut duplicates = Vec::new();
+    for i in sorted {
+        match unique.last() {
+            Some(&last) if points[last] == points[i] => duplicates.push(edge_key((last, i))),
+            _ => unique.push(i),
+        }
+    }
+    (unique, duplicates)
+}
+
+/// Triangles of the Delaunay triangulation of the points, by the index of their corners
+///
+/// No circumcircle of a triangle contains a point and the repeated points are ignored.
+/// `None` if the points are aligned or so close to it (or to a circle) that the rounding
+/// errors broke the triangulation.
+pub fn triangulate(points: &[(f32, f32)]) -> Option<Vec<[usize; 3]>> {
+    let (unique, _) = unique_points(points);
+    bowyer_watson(points, &unique)
+}
+
+/// Sign of the area of the triangle abc: positive if it turns left
+///
+/// It is exact for the coordinates of a canvas: the differences of two `f32`
+/// and their products are exact in `f64`, only the last subtraction is rounded.
+fn orientation(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f64 {
+    let (abx, aby) = (b.0 as f64 - a.0 as f64, b.1 as f64 - a.1 as f64);
+    let (acx, acy) = (c.0 as f64 - a.0 as f64, c.1 as f64 - a.1 as f64);
+    abx * acy - aby * acx
+}
+
+/// Number of points on the border of the convex hull, `unique` is sorted by position
+fn hull_border_count(points: &[(f32, f32)], unique: &[usize]) -> usize {
+    // corners of the hull by Andrew's monotone chain, the aligned points are skipped
+    let mut hull: Vec<usize> = Vec::new();
+    for pass in [unique.to_vec(), unique.iter().rev().copied().collect()] {
+        let start = hull.len();
+        for i in pass {
+            while hull.len() >= start + 2
+                && orientation(
+                    points[hull[hull.len() - 2]],
+                    points[hull[hull.len() - 1]],
+                    points[i],
+                ) <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(i);
+        }
+        // the last point of a chain is the first one of the other
+        hull.pop();
+    }
+    let on_border = |i: usize| {
+        (0..hull.len()).any(|h| {
+            let (a, b) = (points[hull[h]], points[hull[(h + 1) % hull.len()]]);
+            let p = points[i];
+            orientation(a, b, p) == 0.
+                && a.0.min(b.0) <= p.0
+                && p.0 <= a.0.max(b.0)
+                && a.1.min(b.1) <= p.1
+                && p.1 <= a.1.max(b.1)
+        })
+    };
+    unique.iter().filter(|&&i| on_border(i)).count()
+}
+
+/// Insert the points one by one, removing the triangles whose circumcircle contains
+/// the new point and joining the point to the border of the hole they leave
+///
+/// `None` if a triangle is flat or if some triangles are missing: a triangulation
+/// of `n` points with `h` of them on the border of their hull has `2n - 2 - h` triangles.
+/// It happens when the points are aligned or when the super triangle is too small
+/// for the points on the hull that are almost aligned.
+fn bowyer_watson(points: &[(f32, f32)], unique: &[usize]) -> Option<Vec<[usize; 3]>> {
+    if unique.len() < 3 {
+        return Some(Vec::new());
+    }
+    let mut vertices: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x as f64, y as f64)).collect();
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for &i in unique.iter() {
+        let (x, y) = vertices[i];
+        (min_x, min_y) = (min_x.min(x), min_y.min(y));
+        (max_x, max_y) = (max_x.max(x), max_y.max(y));
+    }
+    let size = (max_x - min_x).max(max_y - min_y).max(1.) * SUPER_TRIANGLE_SCALE;
+    let (center_x, center_y) = ((min_x + max_x) / 2., (min_y + max_y) / 2.);
+    let n_points = vertices.len();
+    vertices.extend([
+        (center_x - size, center_y - size),
+        (center_x + size, center_y - size),
+        (center_x, center_y + size),
+    ]);
+
+    let mut triangles: Vec<Triangle> =
+        Triangle::new([n_points, n_points + 1, n_points + 2], &vertices)
+            .into_iter()
+            .collect();
+    for &p in unique.iter() {
+        let (bad, good): (Vec<Triangle>, Vec<Triangle>) = triangles
+            .into_iter()
+            .partition(|triangle| triangle.circumcircle_contains(vertices[p]));
+        triangles = good;
+
+        // the border of the hole is made of the edges of a single removed triangle
+        let mut edges: HashMap<(usize, usize), ((usize, usize), usize)> = HashMap::new();
+        for triangle in bad.iter() {
+            for edge in triangle.edges() {
+                edges.entry(edge_key(edge)).or_insert((edge, 0)).1 += 1;
+            }
+        }
+        for ((a, b), count) in edges.into_values() {
+            if count == 1 {
+                triangles.push(Triangle::new([a, b, p], &vertices)?);
+            }
+        }
+    }
+
+    let triangles: Vec<[usize; 3]> = triangles
+        .into_iter()
+        .map(|triangle| triangle.vertices)
+        .filter(|vertices| vertices.iter().all(|&v| v < n_points))
+        .collect();
+    if triangles.len() + 2 + hull_border_count(points, unique) == 2 * unique.len() {
+        Some(triangles)
+    } else {
+        None
+    }
+}
+
+/// Edges between two points through which goes a circle with no point inside it,
+/// in O(n³): the union of the Delaunay triangulations, used when the points are degenerate
+///
+/// The centers of the circles through i and j are on the bisector of ij, at `m + t u`
+/// with m the middle of ij and u orthogonal to it. A point k on the left of ij is
+/// inside the circle if t is too big, a point on the right if t is too small.
+/// The aligned points are joined in order and the four corners of a rectangle
+/// are joined by its two diagonals.
+fn empty_circle_edges(points: &[(f32, f32)], unique: &[usize]) -> Vec<(usize, usize)> {
+    let point = |i: usize| (points[i].0 as f64, points[i].1 as f64);
+    let mut edges = Vec::new();
+    for (a, &i) in unique.iter().enumerate() {
+        for &j in unique[a + 1..].iter() {
+            let ((xi, yi), (xj, yj)) = (point(i), point(j));
+            let (mx, my) = ((xi + xj) / 2., (yi + yj) / 2.);
+            let (ux, uy) = (yi - yj, xj - xi);
+            let radius2 = (xi - mx).powi(2) + (yi - my).powi(2);
+            let (mut min_t, mut max_t) = (f64::NEG_INFINITY, f64::INFINITY);
+            // the points on the same circle give min_t == max_t up to the rounding errors
+            let empty = |min_t: f64, max_t: f64| {
+                min_t <= max_t + 1e-9 * (1. + min_t.abs().min(max_t.abs()))
+            };
+            let mut between = false;
+            for &k in unique.iter() {
+                if k == i || k == j {
+                    continue;
+                }
+                let (xk, yk) = point(k);
+                let side = ux * (xk - mx) + uy * (yk - my);
+                let bound = ((xk - mx).powi(2) + (yk - my).powi(2) - radius2) / 2.;
+                if side > 0. {
+                    max_t = max_t.min(bound / side);
+                } else if side < 0. {
+                    min_t = min_t.max(bound / side);
+                } else {
+                    // aligned with i and j, inside the circle if it is between them
+                    between = bound < 0.;
+                }
+                if between || !empty(min_t, max_t) {
+                    break;
+                }
+            }
+            if !between && empty(min_t, max_t) {
+                edges.push(edge_key((i, j)));
+            }
+        }
+    }
+    edges
+}
+
+/// Edges of the Delaunay triangulation, every one once with its smallest index first
+///
+/// The repeated points are joined to the point they repeat and the points
+/// all on the same line are joined in order, so that the edges always connect every point.
+/// When the triangulation is broken by aligned or cocircular points, every edge
+/// of every Delaunay triangulation is given: the diagonals of a rectangle cross.
+pub fn delaunay_edges(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+    delaunay_edges_(points).0
+}
+
+/// `delaunay_edges` and whether the points were degenerate
+fn delaunay_edges_(points: &[(f32, f32)]) -> (Vec<(usize, usize)>, bool) {
+    let (unique, mut edges) = unique_points(points);
+    let degenerate = match bowyer_watson(points, &unique) {
+        Some(triangles) if !triangles.is_empty() || unique.len() < 3 => {
+            for [a, b, c] in triangles {
+                edges.extend([(a, b), (b, c), (c, a)].map(edge_key));
+            }
+            // a single edge between the two points
+            if unique.len() == 2 {
+                edges.push(edge_key((unique[0], unique[1])));
+            }
+            false
+        }
+        _ => {
+            edges.extend(empty_circle_edges(points, &unique));
+            true
+        }
+    };
+    edges.sort_unstable();
+    edges.dedup();
+    (edges, degenerate)
+}
+
+fn distance(points: &[(f32, f32)], i: usize, j: usize) -> f32 {
+    let ((xi, yi), (xj, yj)) = (points[i], points[j]);
+    (xj - xi).hypot(yj - yi)
+}
+
+/// Edges of the Euclidean minimum spanning tree of the points
+///
+/// The tree only uses the O(n) edges of the Delaunay triangulation,
+/// so the O(n²) edges of the complete graph are never built,
+/// except when the points are too degenerate to be triangulated.
+pub fn euclidean_mst(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+    let (edges, degenerate) = delaunay_edges_(points);
+    if degenerate {
+        return dense_prim(points);
+    }
+    let edges: Vec<(usize, usize, f32)> = edges
+        .into_iter()
+        .map(|(i, j)| (i, j, distance(points, i, j)))
+        .collect();
+    let tree: Vec<(usize, usize)> = kruskal(points.len(), &edges)
+        .into_iter()
+        .map(|e| (edges[e].0, edges[e].1))
+        .collect();
+    if tree.len() + 1 >= points.len() {
+        tree
+    } else {
+        // a triangulation broken by rounding errors misses some edges
+        dense_prim(points)
+    }
+}
+
+/// Prim's algorithm on the complete graph, computing the distances when they are needed
+fn dense_prim(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+    let n = points.len();
+    let mut tree = Vec::new();
+    let mut in_tree = vec![false; n];
+    // closest node of the tree to every node, with its distance
+    let mut closest: Vec<(f32, usize)> = vec![(f32::INFINITY, 0); n];
+    let mut current = 0;
+    for _ in 1..n {
+        in_tree[current] = true;
+        let mut next = None;
+        for i in 0..n {
+            if in_tree[i] {
+                continue;
+            }
+            let d = distance(points, current, i);
+            if d < closest[i].0 {
+                closest[i] = (d, current);
+            }
+            if next.map_or(true, |j: usize| closest[i].0 < closest[j].0) {
+                next = Some(i);
+            }
+        }
+        let Some(next) = next else {
+            break;
+        };
+        tree.push(edge_key((closest[next].1, next)));
+        current = next;
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::test_inputs::{pairs, random_points};
+    use crate::generator::Rng;
+    use crate::spanning_tree::prim;
+
+    fn length(points: &[(f32, f32)], tree: &[(usize, usize)]) -> f32 {
+        tree.iter().map(|&(i, j)| distance(points, i, j)).sum()
+    }
+
+    /// Length of the minimum spanning tree of the complete graph
+    fn brute_force_mst_length(points: &[(f32, f32)]) -> f32 {
+        let edges: Vec<(usize, usize, f32)> = pairs(points.len())
+            .map(|(i, j)| (i, j, distance(points, i, j)))
+            .collect();
+        prim(points.len(), &edges)
+            .into_iter()
+            .map(|e| edges[e].2)
+            .sum()
+    }
+
+    /// The Euclidean MST spans the points, is as short as the brute force one
+    /// and only uses edges of the triangulation
+    fn assert_mst(points: &[(f32, f32)]) {
+        let tree = euclidean_mst(points);
+        assert_eq!(tree.len(), points.len().saturating_sub(1), "{points:?}");
+        let (expected, length) = (brute_force_mst_length(points), length(points, &tree));
+        assert!(
+            (expected - length).abs() <= 1e-4 * expected.max(1.),
+            "{length} instead of {expected} for {points:?}"
+        );
+        let delaunay = delaunay_edges(points);
+        for edge in tree {
+            assert!(delaunay.contains(&edge_key(edge)), "{edge:?} of {points:?}");
+        }
+    }
+
+    /// Integer points on a circle of radius 5 and its center
+    fn cocircular() -> Vec<(f32, f32)> {
+        let mut points = vec![(0., 0.)];
+        for (x, y) in [(5., 0.), (3., 4.), (4., 3.)] {
+            points.extend([(x, y), (-x, y), (x, -y), (-x, -y), (y, x), (-y, -x)]);
+        }
+        points
+    }
+
+    #[test]
+    fn same_mst_as_brute_force_on_random_points() {
+        let mut rng = Rng::new(6);
+        for n in 0..60 {
+            assert_mst(&random_points(&mut rng, n, 1000.));
+        }
+        // tiny and huge coordinates
+        assert_mst(&random_points(&mut rng, 30, 1e-3));
+        assert_mst(&random_points(&mut rng, 30, 1e7));
+    }
+
+    #[test]
+    fn same_mst_as_brute_force_on_aligned_points() {
+        let mut rng = Rng::new(7);
+        let horizontal: Vec<(f32, f32)> = (0..20).map(|_| (rng.range_f32(0., 500.), 3.)).collect();
+        assert_mst(&horizontal);
+        let diagonal: Vec<(f32, f32)> = (0..20).map(|i| (i as f32 * 7., i as f32 * 7.)).collect();
+        assert_mst(&diagonal);
+        // a long line of points with one of them slightly off
+        let mut almost: Vec<(f32, f32)> = (0..20).map(|i| (i as f32 * 1000., 0.)).collect();
+        almost.push((10500., 0.001));
+        assert_mst(&almost);
+        // every other point slightly off, so that the hull is almost flat
+        let zigzag: Vec<(f32, f32)> = (0..20)
+            .map(|i| (i as f32 * 1000., (i % 2) as f32 * 0.01))
+            .collect();
+        assert_mst(&zigzag);
+    }
+
+    #[test]
+    fn same_mst_as_brute_force_on_cocircular_points() {
+        assert_mst(&cocircular());
+        assert_mst(&cocircular()[1..]);
+        // a grid has four points on a circle everywhere
+        let grid: Vec<(f32, f32)> = (0..49)
+            .map(|i| ((i % 7) as f32 * 50., (i / 7) as f32 * 50.))
+            .collect();
+        assert_mst(&grid);
+        let regular: Vec<(f32, f32)> = (0..24)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / 24.;
+                (300. * angle.cos(), 300. * angle.sin())
+            })
+            .collect();
+        assert_mst(&regular);
+    }
+
+    #[test]
+    fn same_mst_as_brute_force_with_repeated_points() {
+        let mut rng = Rng::new(8);
+        let mut points = random_points(&mut rng, 20, 100.);
+        points.extend(points.clone()[..10].iter());
+        assert_mst(&points);
+        assert_mst(&[(1., 1.); 5]);
+    }
+
+    #[test]
+    fn the_triangulation_of_random_points_is_the_delaunay_one() {
+        let mut rng = Rng::new(9);
+        for n in 3..40 {
+            let points = random_points(&mut rng, n, 1000.);
+            let (unique, _) = unique_points(&points);
+            // in general position, only one triangulation has empty circles
+            let mut expected = empty_circle_edges(&points, &unique);
+            expected.sort_unstable();
+            assert_eq!(delaunay_edges(&points), expected);
+            assert!(triangulate(&points).is_some());
+        }
+    }
+
+    #[test]
+    fn degenerate_points_are_detected() {
+        assert_eq!(triangulate(&[(0., 0.), (1., 1.), (2., 2.)]), None);
+        assert_eq!(
+            delaunay_edges(&[(0., 0.), (2., 2.), (1., 1.)]),
+            vec![(0, 2), (1, 2)]
+        );
+        // a square can be cut by either diagonal
+        let square = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)];
+        let edges = delaunay_edges(&square);
+        for side in [(0, 1), (1, 2), (2, 3), (0, 3)] {
+            assert!(edges.contains(&side));
+        }
+        assert_eq!(
+            triangulate(&[(0., 0.), (4., 0.), (0., 3.)]).map(|triangles| triangles.len()),
+            Some(1)
+        );
+    }
+}
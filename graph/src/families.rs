@@ -0,0 +1,373 @@
+use crate::generator::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Standard graph used to teach and to test the algorithms
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GraphFamily {
+    /// K_n: every pair of the `n` nodes is joined
+    Complete { n: usize },
+    /// C_n: `n` nodes around a circle
+    Cycle { n: usize },
+    /// `n` nodes on a line
+    Path { n: usize },
+    /// A center joined to `n` leaves
+    Star { n: usize },
+    /// A cycle of `n` nodes all joined to a hub
+    Wheel { n: usize },
+    /// `rows` × `columns` nodes joined to their horizontal and vertical neighbours
+    Grid { rows: usize, columns: usize },
+    /// Q_d: the 2^d corners of a cube of dimension `dimension`,
+    /// at most `MAX_HYPERCUBE_DIMENSION`
+    Hypercube { dimension: usize },
+    /// The ten nodes of the Petersen graph
+    Petersen,
+    /// K_{m,n}: every one of `m` nodes joined to every one of `n` other nodes
+    CompleteBipartite { m: usize, n: usize },
+    /// Uniform random tree of `n` nodes, the same seed always gives the same tree
+    RandomTree { n: usize, seed: u64 },
+}
+
+impl Default for GraphFamily {
+    fn default() -> Self {
+        GraphFamily::Complete { n: 5 }
+    }
+}
+
+/// Largest dimension of a hypercube, a bigger one is built with this dimension
+pub const MAX_HYPERCUBE_DIMENSION: usize = 10;
+
+impl GraphFamily {
+    /// Every family with a default parameter
+    pub const ALL: [GraphFamily; 10] = [
+        GraphFamily::Complete { n: 5 },
+        GraphFamily::Cycle { n: 6 },
+        GraphFamily::Path { n: 5 },
+        GraphFamily::Star { n: 6 },
+        GraphFamily::Wheel { n: 6 },
+        GraphFamily::Grid {
+            rows: 3,
+            columns: 4,
+        },
+        GraphFamily::Hypercube { dimension: 3 },
+        GraphFamily::Petersen,
+        GraphFamily::CompleteBipartite { m: 3, n: 3 },
+        GraphFamily::RandomTree { n: 10, seed: 0 },
+    ];
+
+    /// Checks if the two graphs are of the same family, whatever their parameters
+    pub fn same_kind(&self, other: &GraphFamily) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Positions of the nodes around (0, 0) and `adjacencies[i]` the neighbours of the i-th one,
+    /// the joined nodes are about `spacing` apart
+    ///
+    /// The nodes are on a circle, on a grid, in two columns for the bipartite graphs
+    /// or in rows by depth for the trees.
+    pub fn build(&self, spacing: f32) -> (Vec<(f32, f32)>, Vec<Vec<usize>>) {
+        let (positions, edges) = match *self {
+            GraphFamily::Complete { n } => (
+                circle(n, spacing),
+                (0..n)
+                    .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                    .collect(),
+            ),
+            GraphFamily::Cycle { n } => (circle(n, spacing), cycle_edges(0, n)),
+            GraphFamily::Path { n } => (
+                (0..n)
+                    .map(|i| ((i as f32 - (n as f32 - 1.) / 2.) * spacing, 0.))
+                    .collect(),
+                (1..n).map(|i| (i - 1, i)).collect(),
+            ),
+            GraphFamily::Star { n } => {
+                let mut positions = vec![(0., 0.)];
+                positions.extend(circle_with_radius(
+                    n,
+                    circle_radius(n, spacing).max(spacing),
+                ));
+                (positions, (1..=n).map(|i| (0, i)).collect())
+            }
+            GraphFamily::Wheel { n } => {
+                let mut positions = vec![(0., 0.)];
+                positions.extend(circle_with_radius(
+                    n,
+                    circle_radius(n, spacing).max(spacing),
+                ));
+                let mut edges: Vec<(usize, usize)> = (1..=n).map(|i| (0, i)).collect();
+                edges.extend(cycle_edges(1, n));
+                (positions, edges)
+            }
+            GraphFamily::Grid { rows, columns } => {
+                let positions = (0..rows * columns)
+                    .map(|i| {
+                        let (row, column) = (i / columns, i % columns);
+                        (
+                            (column as f32 - (columns as f32 - 1.) / 2.) * spacing,
+                            (row as f32 - (rows as f32 - 1.) / 2.) * spacing,
+                        )
+                    })
+                    .collect();
+                let mut edges = Vec::new();
+                for i in 0..rows * columns {
+                    if i % columns + 1 < columns {
+                        edges.push((i, i + 1));
+                    }
+                    if i + columns < rows * columns {
+                        edges.push((i, i + columns));
+                    }
+                }
+                (positions, edges)
+            }
+            GraphFamily::Hypercube { dimension } => {
+                // in the order of the Gray code, consecutive corners differ by one bit,
+                // so the circle goes along the edges of the cube
+                let dimension = dimension.min(MAX_HYPERCUBE_DIMENSION);
+                let n = 1 << dimension;
+                let mut positions = vec![(0., 0.); n];
+                for (i, pos) in circle(n, spacing).into_iter().enumerate() {
+                    positions[i ^ (i >> 1)] = pos;
+                }
+                let edges = (0..n)
+                    .flat_map(|i| {
+                        (0..dimension)
+                            .map(move |bit| (i, i ^ (1 << bit)))
+                            .filter(|&(i, j)| i < j)
+                    })
+                    .collect();
+                (positions, edges)
+            }
+            GraphFamily::Petersen => {
+                // an outer pentagon and an inner pentagram joined by spokes
+                let radius = circle_radius(5, spacing);
+                let mut positions = circle_with_radius(5, radius);
+                positions.extend(circle_with_radius(5, radius / 2.));
+                let mut edges = cycle_edges(0, 5);
+                edges.extend((0..5).map(|i| (i, i + 5)));
+                edges.extend((0..5).map(|i| (5 + i, 5 + (i + 2) % 5)));
+                (positions, edges)
+            }
+            GraphFamily::CompleteBipartite { m, n } => {
+                let column = |count: usize, x: f32| {
+                    (0..count).map(move |i| (x, (i as f32 - (count as f32 - 1.) / 2.) * spacing))
+                };
+                let x = spacing * (m.max(n) as f32 / 2.).max(1.);
+                let positions = column(m, -x / 2.).chain(column(n, x / 2.)).collect();
+                let edges = (0..m)
+                    .flat_map(|i| (m..m + n).map(move |j| (i, j)))
+                    .collect();
+                (positions, edges)
+            }
+            GraphFamily::RandomTree { n, seed } => {
+                let edges = random_tree(n, seed);
+                (tree_rows(n, &edges, spacing), edges)
+            }
+        };
+
+        let mut adjacencies = vec![Vec::new(); positions.len()];
+        for (i, j) in edges {
+            adjacencies[i].push(j);
+            adjacencies[j].push(i);
+        }
+        (positions, adjacencies)
+    }
+}
+
+impl std::fmt::Display for GraphFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphFamily::Complete { .. } => write!(f, "Complete graph")?,
+            GraphFamily::Cycle { .. } => write!(f, "Cycle")?,
+            GraphFamily::Path { .. } => write!(f, "Path")?,
+            GraphFamily::Star { .. } => write!(f, "Star")?,
+            GraphFamily::Wheel { .. } => write!(f, "Wheel")?,
+            GraphFamily::Grid { .. } => write!(f, "Grid")?,
+            GraphFamily::Hypercube { .. } => write!(f, "Hypercube")?,
+            GraphFamily::Petersen => write!(f, "Petersen graph")?,
+            GraphFamily::CompleteBipartite { .. } => write!(f, "Complete bipartite graph")?,
+            GraphFamily::RandomTree { .. } => write!(f, "Random tree")?,
+        }
+        Ok(())
+    }
+}
+
+/// Radius of the circle where `n` regularly spaced nodes are `spacing` apart
+pub(crate) fn circle_radius(n: usize, spacing: f32) -> f32 {
+    if n < 2 {
+        0.
+    } else {
+        spacing / (2. * (TAU / 2. / n as f32).sin())
+    }
+}
+
+fn circle(n: usize, spacing: f32) -> Vec<(f32, f32)> {
+    circle_with_radius(n, circle_radius(n, spacing))
+}
+
+/// `n` regularly spaced positions on a circle, the first one at the top
+fn circle_with_radius(n: usize, radius: f32) -> Vec<(f32, f32)> {
+    (0..n)
+        .map(|i| {
+            let angle = TAU * i as f32 / n as f32 - FRAC_PI_2;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Edges of the cycle through the `n` nodes starting at `first`
+fn cycle_edges(first: usize, n: usize) -> Vec<(usize, usize)> {
+    match n {
+        0 | 1 => Vec::new(),
+        2 => vec![(first, first + 1)],
+        _ => (0..n).map(|i| (first + i, first + (i + 1) % n)).collect(),
+    }
+}
+
+/// Edges of a tree drawn uniformly among the trees of `n` numbered nodes,
+/// decoded from a random Prüfer sequence
+fn random_tree(n: usize, seed: u64) -> Vec<(usize, usize)> {
+    if n < 3 {
+        return (1..n).map(|i| (i - 1, i)).collect();
+    }
+    let mut rng = Rng::new(seed);
+    let sequence: Vec<usize> = (0..n - 2).map(|_| rng.index(n)).collect();
+    let mut degrees = vec![1; n];
+    for &x in sequence.iter() {
+        degrees[x] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    for &x in sequence.iter() {
+        // the smallest leaf is joined to the next node of the sequence
+        let leaf = (0..n).find(|&i| degrees[i] == 1).unwrap_or(0);
+        edges.push((leaf.min(x), leaf.max(x)));
+        degrees[leaf] -= 1;
+        degrees[x] -= 1;
+    }
+    let last: Vec<usize> = (0..n).filter(|&i| degrees[i] == 1).collect();
+    edges.push((last[0], last[1]));
+    edges
+}
+
+/// Positions of the nodes of a tree rooted at the first node, in one row per depth
+fn tree_rows(n: usize, edges: &[(usize, usize)], spacing: f32) -> Vec<(f32, f32)> {
+    let mut neighbours = vec![Vec::new(); n];
+    for &(i, j) in edges {
+        neighbours[i].push(j);
+        neighbours[j].push(i);
+    }
+    let mut rows: Vec<Vec<usize>> = Vec::new();
+    let mut depths: Vec<Option<usize>> = vec![None; n];
+    let mut queue = std::collections::VecDeque::new();
+    if n > 0 {
+        depths[0] = Some(0);
+        queue.push_back(0);
+    }
+    while let Some(node) = queue.pop_front() {
+        let depth = depths[node].unwrap_or(0);
+        if rows.len() <= depth {
+            rows.push(Vec::new());
+        }
+        rows[depth].push(node);
+        for &adj in neighbours[node].iter() {
+            if depths[adj].is_none() {
+                depths[adj] = Some(depth + 1);
+                queue.push_back(adj);
+            }
+        }
+    }
+
+    let mut positions = vec![(0., 0.); n];
+    for (depth, row) in rows.iter().enumerate() {
+        for (i, &node) in row.iter().enumerate() {
+            positions[node] = (
+                (i as f32 - (row.len() as f32 - 1.) / 2.) * spacing,
+                (depth as f32 - (rows.len() as f32 - 1.) / 2.) * spacing,
+            );
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Model;
+
+    fn model(family: GraphFamily) -> Model {
+        let (positions, adjacencies) = family.build(100.);
+        Model::new(positions, adjacencies).unwrap()
+    }
+
+    fn degrees(model: &Model) -> Vec<usize> {
+        model
+            .nodes()
+            .iter()
+            .map(|node| model.neighbours(node.id).len())
+            .collect()
+    }
+
+    #[test]
+    fn complete_graphs_have_every_edge() {
+        for n in 0..10 {
+            let model = model(GraphFamily::Complete { n });
+            assert_eq!(model.nodes().len(), n);
+            assert_eq!(model.edges().len(), n * n.saturating_sub(1) / 2);
+        }
+    }
+
+    #[test]
+    fn hypercubes_have_d_times_2_to_the_d_minus_1_edges() {
+        for dimension in 0..=MAX_HYPERCUBE_DIMENSION {
+            let model = model(GraphFamily::Hypercube { dimension });
+            let n = 1 << dimension;
+            assert_eq!(model.nodes().len(), n);
+            assert_eq!(model.edges().len(), dimension * n / 2);
+            assert!(degrees(&model).iter().all(|&degree| degree == dimension));
+        }
+    }
+
+    #[test]
+    fn huge_hypercubes_are_clamped() {
+        let (positions, _) = GraphFamily::Hypercube { dimension: 64 }.build(100.);
+        assert_eq!(positions.len(), 1 << MAX_HYPERCUBE_DIMENSION);
+    }
+
+    #[test]
+    fn the_petersen_graph_is_3_regular_with_15_edges() {
+        let model = model(GraphFamily::Petersen);
+        assert_eq!(model.nodes().len(), 10);
+        assert_eq!(model.edges().len(), 15);
+        assert!(degrees(&model).iter().all(|&degree| degree == 3));
+    }
+
+    #[test]
+    fn random_trees_are_trees() {
+        for n in 0..30 {
+            for seed in 0..5 {
+                let model = model(GraphFamily::RandomTree { n, seed });
+                assert_eq!(model.nodes().len(), n);
+                assert_eq!(model.edges().len(), n.saturating_sub(1));
+                assert!(model.components().count() <= 1);
+                assert!(!model.has_cycle());
+            }
+        }
+    }
+
+    #[test]
+    fn other_families() {
+        let edges = |family| model(family).edges().len();
+        assert_eq!(edges(GraphFamily::Cycle { n: 6 }), 6);
+        assert_eq!(edges(GraphFamily::Cycle { n: 2 }), 1);
+        assert_eq!(edges(GraphFamily::Path { n: 5 }), 4);
+        assert_eq!(edges(GraphFamily::Star { n: 6 }), 6);
+        assert_eq!(edges(GraphFamily::Wheel { n: 6 }), 12);
+        assert_eq!(
+            edges(GraphFamily::Grid {
+                rows: 3,
+                columns: 4
+            }),
+            17
+        );
+        assert_eq!(edges(GraphFamily::CompleteBipartite { m: 3, n: 4 }), 12);
+    }
+}
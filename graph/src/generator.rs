@@ -0,0 +1,419 @@
+use crate::delaunay::euclidean_mst;
+use crate::level::{House, Level};
+use crate::proximity::ProximityGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Textures of the houses of the app, a random one is given to every house
+const HOUSE_TEXTURES: [&str; 5] = ["house1", "house2", "house3", "house4", "house5"];
+
+/// Failed attempts before the Poisson disk placement accepts houses closer to each other
+const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// Pseudo random numbers from a seed (SplitMix64),
+/// the same seed always gives the same numbers on every platform
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in [min, max)
+    pub(crate) fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Uniform in [min, max]
+    pub(crate) fn range_u32(&mut self, min: u32, max: u32) -> u32 {
+        let span = max.saturating_sub(min) as u64 + 1;
+        min + (self.next_u64() % span) as u32
+    }
+
+    /// Uniform in [0, n)
+    pub(crate) fn index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Standard normal distribution (Box-Muller transform)
+    pub(crate) fn normal(&mut self) -> f32 {
+        let u1 = 1. - self.next_f32();
+        let u2 = self.next_f32();
+        (-2. * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+    }
+}
+
+/// How the houses of a generated town are placed
+#[derive(Default, Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Placement {
+    /// Anywhere in the town
+    #[default]
+    Uniform,
+    /// Anywhere, but not too close to the other houses
+    PoissonDisk,
+    /// Around the centers of some villages
+    Clustered { clusters: usize },
+    /// On a grid, every house moved by at most `jitter` times half a cell
+    JitteredGrid { jitter: f32 },
+}
+
+impl Placement {
+    /// Every placement with a default parameter
+    pub const ALL: [Placement; 4] = [
+        Placement::Uniform,
+        Placement::PoissonDisk,
+        Placement::Clustered { clusters: 3 },
+        Placement::JitteredGrid { jitter: 0.5 },
+    ];
+
+    /// Checks if the two placements are the same, whatever their parameter
+    pub fn same_kind(&self, other: &Placement) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl std::fmt::Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Placement::Uniform => write!(f, "Uniform")?,
+            Placement::PoissonDisk => write!(f, "Poisson disk")?,
+            Placement::Clustered { .. } => write!(f, "Clustered")?,
+            Placement::JitteredGrid { .. } => write!(f, "Jittered grid")?,
+        }
+        Ok(())
+    }
+}
+
+/// Random town of the Muddy City game, the same settings always give the same town
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct TownGenerator {
+    pub seed: u64,
+    pub houses: usize,
+    pub placement: Placement,
+    /// Size of the town, the houses are at least `margin` away from its border
+    pub width: f32,
+    pub height: f32,
+    pub margin: f32,
+    /// Candidate roads, the roads of the shortest covering tree are always added
+    /// so that the town can be connected
+    pub roads: ProximityGraph,
+    /// Paving cost of the roads, drawn uniformly between the two (included)
+    pub min_cost: u32,
+    pub max_cost: u32,
+}
+
+impl Default for TownGenerator {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            houses: 12,
+            placement: Placement::default(),
+            width: 1200.,
+            height: 800.,
+            margin: 60.,
+            roads: ProximityGraph::default(),
+            min_cost: 1,
+            max_cost: 9,
+        }
+    }
+}
+
+impl TownGenerator {
+    /// Generate the town, with its settings in its name
+    pub fn generate(&self) -> Level {
+        let mut rng = Rng::new(self.seed);
+        // the positions are rounded to be written in a level file, before the roads
+        // are chosen so that they are the ones of the positions of the level
+        let positions = self.rounded(self.positions(&mut rng));
+
+        let mut edges = self.roads.edges(&positions);
+        edges.extend(euclidean_mst(&positions));
+        edges.sort_unstable();
+        edges.dedup();
+
+        let houses = positions
+            .into_iter()
+            .map(|pos| House {
+                pos,
+                texture: Some(HOUSE_TEXTURES[rng.index(HOUSE_TEXTURES.len())].to_string()),
+            })
+            .collect();
+        let (min_cost, max_cost) = (
+            self.min_cost.min(self.max_cost),
+            self.min_cost.max(self.max_cost),
+        );
+        let roads = edges
+            .into_iter()
+            .map(|(i, j)| (i, j, rng.range_u32(min_cost, max_cost) as f32))
+            .collect();
+
+        Level {
+            name: format!(
+                "{} town of {} houses (seed {})",
+                self.placement, self.houses, self.seed
+            ),
+            difficulty: (self.houses as u32 / 10 + 1).min(5),
+            cobblestone: true,
+            houses,
+            roads,
+        }
+    }
+
+    /// Bounds of the positions of the houses: (min x, min y, max x, max y)
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        let margin_x = self.margin.min(self.width / 2.);
+        let margin_y = self.margin.min(self.height / 2.);
+        (
+            margin_x,
+            margin_y,
+            self.width - margin_x,
+            self.height - margin_y,
+        )
+    }
+
+    /// Round the positions, a house on the position of another one is moved
+    /// to the nearest free position in the bounds (or outside when they are full)
+    fn rounded(&self, positions: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let (min_x, min_y, max_x, max_y) = (
+            min_x.round() as i64,
+            min_y.round() as i64,
+            max_x.round() as i64,
+            max_y.round() as i64,
+        );
+        // a ring farther than that is out of the bounds
+        let max_ring = (max_x - min_x).max(max_y - min_y) + 1;
+        let in_bounds = |(x, y): (i64, i64)| min_x <= x && x <= max_x && min_y <= y && y <= max_y;
+
+        let mut taken = HashSet::new();
+        positions
+            .into_iter()
+            .map(|(x, y)| {
+                let (x, y) = (x.round() as i64, y.round() as i64);
+                let mut ring = 0;
+                let (x, y) = loop {
+                    // the positions at `ring` steps of (x, y) on both axes
+                    let free = (-ring..=ring)
+                        .flat_map(|dx| (-ring..=ring).map(move |dy| (x + dx, y + dy)))
+                        .filter(|&(cx, cy)| (cx - x).abs().max((cy - y).abs()) == ring)
+                        .find(|&pos| !taken.contains(&pos) && (in_bounds(pos) || ring > max_ring));
+                    if let Some(pos) = free {
+                        break pos;
+                    }
+                    ring += 1;
+                };
+                taken.insert((x, y));
+                (x as f32, y as f32)
+            })
+            .collect()
+    }
+
+    fn positions(&self, rng: &mut Rng) -> Vec<(f32, f32)> {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let uniform = |rng: &mut Rng| (rng.range_f32(min_x, max_x), rng.range_f32(min_y, max_y));
+        match self.placement {
+            Placement::Uniform => (0..self.houses).map(|_| uniform(rng)).collect(),
+            Placement::PoissonDisk => {
+                // the distance of a perfect packing, reduced when no house can be added
+                let area = (max_x - min_x).max(1.) * (max_y - min_y).max(1.);
+                let mut min_distance = (area / self.houses.max(1) as f32).sqrt();
+                let mut positions: Vec<(f32, f32)> = Vec::with_capacity(self.houses);
+                let mut failures = 0;
+                while positions.len() < self.houses {
+                    let (x, y) = uniform(rng);
+                    if positions
+                        .iter()
+                        .all(|&(px, py)| (px - x).hypot(py - y) >= min_distance)
+                    {
+                        positions.push((x, y));
+                        failures = 0;
+                    } else {
+                        failures += 1;
+                        if failures == POISSON_DISK_ATTEMPTS {
+                            min_distance *= 0.9;
+                            failures = 0;
+                        }
+                    }
+                }
+                positions
+            }
+            Placement::Clustered { clusters } => {
+                let centers: Vec<(f32, f32)> = (0..clusters.max(1)).map(|_| uniform(rng)).collect();
+                let spread =
+                    (max_x - min_x).min(max_y - min_y) / (4. * (centers.len() as f32).sqrt());
+                (0..self.houses)
+                    .map(|_| {
+                        let (cx, cy) = centers[rng.index(centers.len())];
+                        (
+                            (cx + spread * rng.normal()).clamp(min_x, max_x),
+                            (cy + spread * rng.normal()).clamp(min_y, max_y),
+                        )
+                    })
+                    .collect()
+            }
+            Placement::JitteredGrid { jitter } => {
+                let (width, height) = ((max_x - min_x).max(1.), (max_y - min_y).max(1.));
+                let columns = ((self.houses as f32 * width / height).sqrt().ceil() as usize).max(1);
+                let rows = (self.houses + columns - 1) / columns;
+                let (cell_width, cell_height) =
+                    (width / columns as f32, height / rows.max(1) as f32);
+                let jitter = jitter.clamp(0., 1.) / 2.;
+                (0..self.houses)
+                    .map(|i| {
+                        let (column, row) = (i % columns, i / columns);
+                        (
+                            min_x
+                                + cell_width
+                                    * (column as f32 + 0.5 + jitter * rng.range_f32(-1., 1.)),
+                            min_y
+                                + cell_height
+                                    * (row as f32 + 0.5 + jitter * rng.range_f32(-1., 1.)),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Random inputs of the tests of the algorithms, the same seed always gives the same inputs
+#[cfg(test)]
+pub(crate) mod test_inputs {
+    use super::Rng;
+    use crate::spanning_tree::WeightedEdge;
+
+    /// Points in the square `[0, size)²`
+    pub(crate) fn random_points(rng: &mut Rng, n: usize, size: f32) -> Vec<(f32, f32)> {
+        (0..n)
+            .map(|_| (rng.range_f32(0., size), rng.range_f32(0., size)))
+            .collect()
+    }
+
+    /// `n_edges` random edges without loops, with some parallel edges when there are many,
+    /// after the edges of a random tree when `connected` is true; weights are in `[0, 100)`
+    pub(crate) fn random_edges(
+        rng: &mut Rng,
+        n_nodes: usize,
+        n_edges: usize,
+        connected: bool,
+    ) -> Vec<WeightedEdge> {
+        let mut edges = Vec::new();
+        if connected {
+            for i in 1..n_nodes {
+                edges.push((rng.index(i), i, rng.range_f32(0., 100.)));
+            }
+        }
+        let mut added = 0;
+        while n_nodes > 1 && added < n_edges {
+            let (i, j) = (rng.index(n_nodes), rng.index(n_nodes));
+            if i != j {
+                edges.push((i, j, rng.range_f32(0., 100.)));
+                added += 1;
+            }
+        }
+        edges
+    }
+
+    /// Every pair of indices below `n`, the smallest first
+    pub(crate) fn pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
+        (0..n).flat_map(move |i| (i + 1..n).map(move |j| (i, j)))
+    }
+
+    pub(crate) fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+        (a.0 - b.0).hypot(a.1 - b.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generators() -> Vec<TownGenerator> {
+        let mut generators = Vec::new();
+        for placement in Placement::ALL {
+            for seed in 0..5 {
+                generators.push(TownGenerator {
+                    seed,
+                    placement,
+                    houses: 30,
+                    ..Default::default()
+                });
+            }
+        }
+        generators
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_town() {
+        for generator in generators() {
+            assert_eq!(generator.generate(), generator.generate());
+        }
+        let generator = TownGenerator::default();
+        let other = TownGenerator {
+            seed: 1,
+            ..generator
+        };
+        assert_ne!(generator.generate().houses, other.generate().houses);
+    }
+
+    #[test]
+    fn the_houses_are_rounded_and_apart() {
+        let crowded = [
+            // more houses than the clusters can hold without stacking them
+            TownGenerator {
+                houses: 300,
+                placement: Placement::Clustered { clusters: 1 },
+                width: 100.,
+                height: 100.,
+                margin: 40.,
+                ..Default::default()
+            },
+            // a town with no room at all
+            TownGenerator {
+                houses: 20,
+                width: 10.,
+                height: 10.,
+                margin: 60.,
+                ..Default::default()
+            },
+        ];
+        for generator in generators().into_iter().chain(crowded) {
+            let level = generator.generate();
+            assert_eq!(level.houses.len(), generator.houses);
+            let mut positions = HashSet::new();
+            for house in level.houses.iter() {
+                let (x, y) = house.pos;
+                assert_eq!((x.round(), y.round()), (x, y));
+                assert!(positions.insert((x as i64, y as i64)), "{generator:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_town_can_be_connected() {
+        for generator in generators() {
+            let level = generator.generate();
+            assert!(level.to_model().components().is_connected());
+            for &(id1, id2, cost) in level.roads.iter() {
+                assert!(id1 < id2 && id2 < level.houses.len());
+                assert!((1. ..=9.).contains(&cost));
+            }
+        }
+    }
+}
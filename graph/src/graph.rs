@@ -1,57 +1,91 @@
-use crate::node::{pos2_to_node, Node};
+use crate::bridges::CriticalParts;
+use crate::camera::Camera;
+use crate::components::Components;
+use crate::families::GraphFamily;
+use crate::highlight::Highlight;
+use crate::history::{Edit, History, Revision};
+use crate::layout::Layout;
+use crate::model::{Model, ModelEdge, ModelNode};
+use crate::node::Node;
+use crate::proximity::ProximityGraph;
+use crate::save::{GraphData, TextureNames};
+use crate::shortest_path::{Path, ShortestPathAlgorithm};
+use crate::spanning_tree::{SpanningForest, SpanningTreeAlgorithm, Step, WeightedEdge};
 use crate::state::State;
 use crate::vertex::{TemporaryVertex, Vertex};
+use crate::weighting::Weighting;
+use std::collections::HashSet;
 
+/// The nodes are known by their id, which does not change when other nodes are removed
 #[derive(Debug, Clone, Default)]
 pub struct Graph {
-    pub nodes: Vec<Node>,
-    pub adjacencies: Vec<Vec<usize>>,
-    vertices: Vec<Vertex>,
-    max_id: usize,
+    /// Nodes, vertices and weighting, the positions and weights are only in the model,
+    /// every `Node` and `Vertex` holds the way it is drawn
+    model: Model<Node, Vertex>,
 
     state: State,
+    camera: Camera,
+    /// Nodes of the selected vertex
+    selected_vertex: Option<(usize, usize)>,
+    selected_nodes: HashSet<usize>,
+    /// Corners of the rectangle drawn to select nodes
+    selection_rect: Option<(egui::Pos2, egui::Pos2)>,
+    /// Move of the dragged nodes since the beginning of the drag
+    drag_delta: egui::Vec2,
+    history: History,
 
     new_vertex: TemporaryVertex,
 
-    new_node: Option<Node>,
+    /// Node added at its position with the key A
+    new_node: Option<(Node, egui::Pos2)>,
 }
 
 impl Graph {
     // CONSTRUCTOR /////////////////////////////////////////////////////////////////
-    pub fn new(nodes: Vec<Node>, adjacencies: Vec<Vec<usize>>) -> Self {
-        let max_id = nodes.len();
-        let mut vertices: Vec<Vertex> = Vec::new();
-
-        for (i, _) in nodes.iter().enumerate() {
-            for j in adjacencies[i].iter() {
-                if i < *j {
-                    vertices.push(Vertex::new(i, *j, None));
+    /// Graph where `adjacencies[i]` holds the ids of the neighbours of `nodes[i]`
+    pub fn new(nodes: Vec<ModelNode<Node>>, adjacencies: Vec<Vec<usize>>) -> Self {
+        let mut edges = Vec::new();
+        for (node, adjacency) in nodes.iter().zip(adjacencies.iter()) {
+            for &j in adjacency.iter() {
+                if node.id < j {
+                    edges.push(ModelEdge {
+                        id1: node.id,
+                        id2: j,
+                        weight: None,
+                        data: Vertex::new(node.id, j, None),
+                    });
                 }
             }
         }
-
-        Self {
-            nodes,
-            adjacencies,
-            max_id,
-            vertices,
-
-            state: State::default(),
-
-            new_vertex: TemporaryVertex::default(),
-
-            new_node: None,
+        Graph {
+            model: Model::from_parts(nodes, adjacencies, edges, Weighting::default()),
+            ..Default::default()
         }
     }
 
+    /// Graph of a standard family centered on `center`, the joined nodes about `spacing` apart
+    pub fn family(
+        family: GraphFamily,
+        center: egui::Pos2,
+        spacing: f32,
+        node_stroke: egui::Stroke,
+    ) -> Self {
+        let (positions, adjacencies) = family.build(spacing);
+        let positions = positions
+            .into_iter()
+            .map(|(x, y)| center + egui::vec2(x, y))
+            .collect();
+        Graph::pos2_to_graph(positions, adjacencies, node_stroke)
+    }
+
     pub fn new_default_with_color(color: egui::Color32) -> Self {
         Graph::default().set_color_nodes(color).to_owned()
     }
 
     // SETTERS /////////////////////////////////////////////////////////////////////
     pub fn set_radius_nodes(&mut self, radius: f32) -> &mut Self {
-        for node in self.nodes.iter_mut() {
-            node.set_radius(radius);
+        for node in self.model.nodes_mut() {
+            node.data.set_radius(radius);
         }
         // set radius of the new vertex
         if let Some(node) = self.new_vertex.first_mut() {
@@ -60,15 +94,15 @@ impl Graph {
         if let Some(node) = self.new_vertex.second_mut() {
             node.set_radius(radius);
         }
-        if let Some(node) = self.new_node.as_mut() {
+        if let Some((node, _)) = self.new_node.as_mut() {
             node.set_radius(radius);
         }
         self
     }
 
     pub fn set_stroke_nodes(&mut self, stroke: egui::Stroke) -> &mut Self {
-        for node in self.nodes.iter_mut() {
-            node.set_stroke(stroke);
+        for node in self.model.nodes_mut() {
+            node.data.set_stroke(stroke);
         }
         // set stroke of the new vertex
         if let Some(node) = self.new_vertex.first_mut() {
@@ -77,22 +111,22 @@ impl Graph {
         if let Some(node) = self.new_vertex.second_mut() {
             node.set_stroke(stroke);
         }
-        if let Some(node) = self.new_node.as_mut() {
+        if let Some((node, _)) = self.new_node.as_mut() {
             node.set_stroke(stroke);
         }
         self
     }
 
     pub fn set_stroke_vertex(&mut self, stroke: egui::Stroke) -> &mut Self {
-        for vertex in self.vertices.iter_mut() {
-            vertex.set_stroke(stroke);
+        for edge in self.model.edges_mut() {
+            edge.data.set_stroke(stroke);
         }
         self
     }
 
     pub fn set_width_nodes(&mut self, width: f32) -> &mut Self {
-        for node in self.nodes.iter_mut() {
-            node.set_width(width);
+        for node in self.model.nodes_mut() {
+            node.data.set_width(width);
         }
         // set width of the new vertex
         if let Some(node) = self.new_vertex.first_mut() {
@@ -101,15 +135,15 @@ impl Graph {
         if let Some(node) = self.new_vertex.second_mut() {
             node.set_width(width);
         }
-        if let Some(node) = self.new_node.as_mut() {
+        if let Some((node, _)) = self.new_node.as_mut() {
             node.set_width(width);
         }
         self
     }
 
     pub fn set_width_vertex(&mut self, width: f32) -> &mut Self {
-        for vertex in self.vertices.iter_mut() {
-            vertex.set_width(width);
+        for edge in self.model.edges_mut() {
+            edge.data.set_width(width);
         }
         // set width temporary vertex
         self.new_vertex.set_width(width);
@@ -118,9 +152,11 @@ impl Graph {
     }
 
     pub fn set_textures_vertex(&mut self, textures_id: Vec<egui::TextureId>) -> &mut Self {
-        for vertex in self.vertices.iter_mut() {
-            vertex.set_textures(textures_id.clone());
+        self.history.begin_group();
+        for index in 0..self.n_vertices() {
+            self.change_vertex(index, |vertex| vertex.set_textures(textures_id.clone()));
         }
+        self.history.end_group();
         self
     }
 
@@ -130,28 +166,47 @@ impl Graph {
         textures_id: Vec<egui::TextureId>,
         size: Vec<egui::Vec2>,
     ) -> &mut Self {
-        for i in 0..self.nodes.len() {
+        self.history.begin_group();
+        for i in 0..self.n_nodes() {
             let alpha: f32 = width_image / size[i % textures_id.len()].x;
-            let pos_node = self.nodes[i].pos();
-            self.nodes[i].set_drawable_image(
-                pos_node,
-                size[i % textures_id.len()] * alpha,
-                textures_id[i % textures_id.len()],
-            );
+            self.change_node(i, |node| {
+                node.set_drawable_image(
+                    size[i % textures_id.len()] * alpha,
+                    textures_id[i % textures_id.len()],
+                );
+            });
+        }
+        self.history.end_group();
+        self
+    }
+
+    /// Draw one node with a texture of width `width_image`
+    pub fn set_texture_node(
+        &mut self,
+        id: usize,
+        width_image: f32,
+        texture_id: egui::TextureId,
+        size: egui::Vec2,
+    ) -> &mut Self {
+        if let Some(index) = self.node_index(id) {
+            let alpha: f32 = width_image / size.x;
+            self.change_node(index, |node| {
+                node.set_drawable_image(size * alpha, texture_id);
+            });
         }
         self
     }
 
     pub fn set_width_cobblestone_vertex(&mut self, width: f32) -> &mut Self {
-        for vertex in self.vertices.iter_mut() {
-            vertex.set_width_cobblestone(width);
+        for edge in self.model.edges_mut() {
+            edge.data.set_width_cobblestone(width);
         }
         self
     }
 
     pub fn set_color_nodes(&mut self, color: egui::Color32) -> &mut Self {
-        for node in self.nodes.iter_mut() {
-            node.set_color(color);
+        for node in self.model.nodes_mut() {
+            node.data.set_color(color);
         }
         // set color of the new vertex
         if let Some(node) = self.new_vertex.first_mut() {
@@ -160,48 +215,459 @@ impl Graph {
         if let Some(node) = self.new_vertex.second_mut() {
             node.set_color(color);
         }
-        if let Some(node) = self.new_node.as_mut() {
+        if let Some((node, _)) = self.new_node.as_mut() {
             node.set_color(color);
         }
         self
     }
 
+    /// Show the weight of every vertex with a font size, or hide it with `None`
+    pub fn set_label_size_vertex(&mut self, label_size: Option<f32>) -> &mut Self {
+        for edge in self.model.edges_mut() {
+            edge.data.set_label_size(label_size);
+        }
+        self
+    }
+
     pub fn set_color_vertex(&mut self, color: egui::Color32) -> &mut Self {
-        for vertex in self.vertices.iter_mut() {
-            vertex.set_color(color);
+        for edge in self.model.edges_mut() {
+            edge.data.set_color(color);
+        }
+        self
+    }
+
+    pub fn set_weighting(&mut self, weighting: Weighting) -> &mut Self {
+        if weighting != self.weighting() {
+            self.model.set_weighting(weighting);
+            self.history.touch();
+        }
+        self
+    }
+
+    /// Set the explicit weight of the vertex between two nodes
+    pub fn set_weight(&mut self, id1: usize, id2: usize, weight: Option<f32>) -> &mut Self {
+        if let Some(edit) = self.weight_edit(id1, id2, weight) {
+            self.do_edit(edit);
+        }
+        self
+    }
+
+    /// Set the explicit weight of the vertex between two nodes while it is dragged,
+    /// the whole drag is a single step of the history until `end_weight_drag`
+    pub fn drag_weight(&mut self, id1: usize, id2: usize, weight: Option<f32>) -> &mut Self {
+        if let Some(edit) = self.weight_edit(id1, id2, weight) {
+            self.apply(edit.clone());
+            self.history.push_weight_drag(edit);
+        }
+        self
+    }
+
+    /// End the drag of a weight, its next change is a new step of the history
+    pub fn end_weight_drag(&mut self) -> &mut Self {
+        self.history.end_weight_drag();
+        self
+    }
+
+    /// Edit setting the explicit weight of a vertex, if it changes it
+    fn weight_edit(&self, id1: usize, id2: usize, weight: Option<f32>) -> Option<Edit> {
+        let before = self.model.edge(id1, id2)?.weight;
+        (before != weight).then_some(Edit::SetWeight {
+            id1,
+            id2,
+            before,
+            after: weight,
+        })
+    }
+
+    /// Highlight the vertex at `index` (same order as `weighted_edges`)
+    pub fn set_highlight_vertex(&mut self, index: usize, highlight: Highlight) -> &mut Self {
+        if let Some(edge) = self.model.edges_mut().get_mut(index) {
+            edge.data.set_highlight(highlight);
         }
         self
     }
 
+    /// Highlight the node with the id `id`
+    pub fn set_highlight_node(&mut self, id: usize, highlight: Highlight) -> &mut Self {
+        if let Some(node) = self.model.node_data_mut(id) {
+            node.set_highlight(highlight);
+        }
+        self
+    }
+
+    /// Use the camera of another graph, to draw both at the same place
+    pub fn set_camera(&mut self, camera: Camera) -> &mut Self {
+        self.camera = camera;
+        self
+    }
+
+    /// Move the camera to show every node in `rect`
+    pub fn fit_to_view(&mut self, rect: egui::Rect) -> &mut Self {
+        let positions: Vec<egui::Pos2> = self
+            .model
+            .nodes()
+            .iter()
+            .map(|node| node.pos.into())
+            .collect();
+        if !positions.is_empty() {
+            self.camera.fit(egui::Rect::from_points(&positions), rect);
+        }
+        self
+    }
+
+    /// Select the vertex between two nodes, or nothing with `None`
+    pub fn set_selected_vertex(&mut self, selected: Option<(usize, usize)>) -> &mut Self {
+        self.selected_vertex = selected.filter(|&(id1, id2)| self.vertex_index(id1, id2).is_some());
+        self
+    }
+
+    /// Select the nodes with the ids `ids`
+    pub fn set_selected_nodes(&mut self, ids: &[usize]) -> &mut Self {
+        self.selected_nodes = ids
+            .iter()
+            .copied()
+            .filter(|&id| self.node_index(id).is_some())
+            .collect();
+        self
+    }
+
+    /// Draw a node as a circle
+    pub fn set_circle_node(&mut self, id: usize, stroke: egui::Stroke) -> &mut Self {
+        if let Some(index) = self.node_index(id) {
+            self.change_node(index, |node| {
+                node.set_drawable_circle(stroke);
+            });
+        }
+        self
+    }
+
+    /// Draw the vertex between two nodes as a line
+    pub fn set_straight(&mut self, id1: usize, id2: usize, stroke: egui::Stroke) -> &mut Self {
+        if let Some(index) = self.vertex_index(id1, id2) {
+            self.change_vertex(index, |vertex| vertex.set_straight(stroke));
+        }
+        self
+    }
+
+    /// Draw the vertex between two nodes with textures
+    pub fn set_textures(
+        &mut self,
+        id1: usize,
+        id2: usize,
+        textures_id: Vec<egui::TextureId>,
+    ) -> &mut Self {
+        if let Some(index) = self.vertex_index(id1, id2) {
+            self.change_vertex(index, |vertex| vertex.set_textures(textures_id));
+        }
+        self
+    }
+
+    /// Highlight every node and vertex with the colour of its connected component
+    pub fn highlight_components(&mut self) -> &mut Self {
+        let components = self.components();
+        let labels: Vec<usize> = self
+            .model
+            .edges()
+            .iter()
+            .map(|edge| {
+                self.model
+                    .node_index(edge.id1)
+                    .map_or(0, |i| components.label(i))
+            })
+            .collect();
+        for (index, node) in self.model.nodes_mut().iter_mut().enumerate() {
+            node.data
+                .set_highlight(Highlight::Component(components.label(index)));
+        }
+        for (edge, label) in self.model.edges_mut().iter_mut().zip(labels) {
+            edge.data.set_highlight(Highlight::Component(label));
+        }
+        self
+    }
+
+    /// Highlight the bridges and the articulation points
+    pub fn highlight_critical_parts(&mut self) -> &mut Self {
+        let parts = self.critical_parts();
+        for e in parts.bridges {
+            self.set_highlight_vertex(e, Highlight::Critical);
+        }
+        for id in parts.articulation_points {
+            self.set_highlight_node(id, Highlight::Critical);
+        }
+        self
+    }
+
+    pub fn clear_highlights(&mut self) -> &mut Self {
+        for edge in self.model.edges_mut() {
+            edge.data.set_highlight(Highlight::None);
+        }
+        for node in self.model.nodes_mut() {
+            node.data.set_highlight(Highlight::None);
+        }
+        self
+    }
+
+    // GETTERS /////////////////////////////////////////////////////////////////////
+    /// The graph without its selections, camera and history
+    pub fn model(&self) -> &Model<Node, Vertex> {
+        &self.model
+    }
+
+    pub fn n_nodes(&self) -> usize {
+        self.model.nodes().len()
+    }
+
+    pub fn n_vertices(&self) -> usize {
+        self.model.edges().len()
+    }
+
+    pub fn weighting(&self) -> Weighting {
+        self.model.weighting()
+    }
+
+    /// Every node, in order
+    fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.model.nodes().iter().map(|node| &node.data)
+    }
+
+    /// Every vertex, in order
+    fn vertices(&self) -> impl Iterator<Item = &Vertex> {
+        self.model.edges().iter().map(|edge| &edge.data)
+    }
+
+    pub fn camera(&self) -> Camera {
+        self.camera
+    }
+
+    /// Position of the pointer in the graph
+    fn pointer_pos(&self) -> Option<egui::Pos2> {
+        self.camera
+            .pointer()
+            .map(|pointer| self.camera.to_world(pointer))
+    }
+
+    pub fn selected_vertex(&self) -> Option<(usize, usize)> {
+        self.selected_vertex
+    }
+
+    /// Ids of the selected nodes, in increasing order
+    pub fn selected_nodes(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.selected_nodes.iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Nodes of every vertex between two selected nodes
+    pub fn selected_vertices(&self) -> Vec<(usize, usize)> {
+        self.model
+            .edges()
+            .iter()
+            .map(|edge| (edge.id1, edge.id2))
+            .filter(|(id1, id2)| {
+                self.selected_nodes.contains(id1) && self.selected_nodes.contains(id2)
+            })
+            .collect()
+    }
+
+    /// Checks if the vertex between two nodes is drawn with textures
+    pub fn is_cobblestone(&self, id1: usize, id2: usize) -> bool {
+        self.model
+            .edge_data(id1, id2)
+            .map_or(false, |vertex| vertex.is_cobblestone())
+    }
+
+    /// Index in `nodes` and `adjacencies` of the node with the id `id`
+    pub fn node_index(&self, id: usize) -> Option<usize> {
+        self.model.node_index(id)
+    }
+
+    /// Position of the node with the id `id`, the nodes of the vertices are always in the graph
+    fn node_pos(&self, id: usize) -> egui::Pos2 {
+        self.model
+            .pos(id)
+            .map_or(egui::Pos2::ZERO, egui::Pos2::from)
+    }
+
+    /// Id and position of every node, in the order of the nodes
+    pub fn node_positions(&self) -> Vec<(usize, egui::Pos2)> {
+        self.model
+            .nodes()
+            .iter()
+            .map(|node| (node.id, node.pos.into()))
+            .collect()
+    }
+
+    /// Id of the node under a point of the graph, the last drawn if there are several
+    pub fn node_at(&self, pos: egui::Pos2) -> Option<usize> {
+        self.model
+            .nodes()
+            .iter()
+            .rev()
+            .find(|node| node.data.contains(node.pos.into(), pos))
+            .map(|node| node.id)
+    }
+
+    /// Index of the vertex between two nodes
+    pub fn vertex_index(&self, id1: usize, id2: usize) -> Option<usize> {
+        self.model.edge_index(id1, id2)
+    }
+
+    /// Weight of the vertex between two nodes with the weighting of the graph
+    pub fn weight(&self, id1: usize, id2: usize) -> Option<f32> {
+        self.model.weight(id1, id2)
+    }
+
+    /// Distance between two nodes, `None` if one of them is not in the graph
+    pub fn distance(&self, id1: usize, id2: usize) -> Option<f32> {
+        self.model.distance(id1, id2)
+    }
+
+    /// Explicit weight of the vertex between two nodes, if it has one
+    pub fn explicit_weight(&self, id1: usize, id2: usize) -> Option<f32> {
+        self.model.edge(id1, id2).and_then(|edge| edge.weight)
+    }
+
+    /// Nodes of the vertex under a point of the graph (not of the screen)
+    /// a point on a node is never on a vertex
+    pub fn vertex_at(&self, pos: egui::Pos2) -> Option<(usize, usize)> {
+        if self.node_at(pos).is_some() {
+            return None;
+        }
+        self.model
+            .edges()
+            .iter()
+            .find(|edge| {
+                edge.data
+                    .contains(pos, self.node_pos(edge.id1), self.node_pos(edge.id2))
+            })
+            .map(|edge| (edge.id1, edge.id2))
+    }
+
     // DRAW /////////////////////////////////////////////////////////////////////
+    /// Draw the graph on the screen through its camera
     pub fn draw(&mut self, ui: &mut egui::Ui) {
+        let camera = self.camera;
+        let zoom = camera.zoom();
+        let screen_pos = |id| camera.to_screen(self.node_pos(id));
+
+        // draw the selected and the hovered vertices under the others
+        let hovered = match self.state {
+            State::Idle if ui.ui_contains_pointer() => {
+                self.pointer_pos().and_then(|pos| self.vertex_at(pos))
+            }
+            _ => None,
+        };
+        let halos = [
+            (hovered, ui.visuals().widgets.hovered.bg_fill),
+            (self.selected_vertex, ui.visuals().selection.bg_fill),
+        ];
+        for (vertex, color) in halos {
+            let Some((id1, id2)) = vertex else {
+                continue;
+            };
+            if let Some(vertex) = self.model.edge_data(id1, id2) {
+                vertex
+                    .scaled(zoom)
+                    .draw_halo(ui, screen_pos(id1), screen_pos(id2), color);
+            }
+        }
+
         // draw every vertex
-        for vertex in self.vertices.iter() {
-            vertex.draw(
+        for edge in self.model.edges() {
+            edge.data.scaled(zoom).draw(
                 ui,
-                self.nodes[vertex.node_id1()].pos(),
-                self.nodes[vertex.node_id2()].pos(),
+                screen_pos(edge.id1),
+                screen_pos(edge.id2),
+                self.model.edge_weight(edge),
             );
         }
 
-        // draw every node
-        for node in self.nodes.iter() {
-            node.draw(ui);
+        // draw every node, the selected and highlighted ones with a halo
+        let selection_color = ui.visuals().selection.bg_fill;
+        for node in self.model.nodes() {
+            let pos = camera.to_screen(node.pos.into());
+            let drawn = node.data.scaled(zoom);
+            if self.selected_nodes.contains(&node.id) {
+                drawn.draw_halo(ui, pos, selection_color);
+            } else if let Some(color) = drawn.highlight().color() {
+                drawn.draw_halo(ui, pos, color);
+            }
+            drawn.draw(ui, pos);
+        }
+
+        if let Some((corner1, corner2)) = self.selection_rect {
+            let rect =
+                egui::Rect::from_two_pos(camera.to_screen(corner1), camera.to_screen(corner2));
+            ui.painter().rect(
+                rect,
+                0.,
+                selection_color.linear_multiply(0.2),
+                ui.visuals().selection.stroke,
+            );
         }
 
         // draw the new vertex
         if self.new_vertex.first_is_some() {
-            self.new_vertex.draw(ui);
+            self.new_vertex.draw(ui, &camera);
         }
 
-        if let Some(node) = self.new_node {
-            node.draw(ui);
+        if let Some((node, pos)) = self.new_node {
+            node.scaled(zoom).draw(ui, camera.to_screen(pos));
         }
     }
+    /// Update the graph with an event whose position is on the screen
+    #[allow(clippy::collapsible_match, clippy::single_match)]
     pub fn update(&mut self, event: &egui::Event) {
+        if self.update_camera(event) {
+            return;
+        }
+        let event = &self.camera.event_to_world(event);
+
+        if self.update_selection(event) {
+            return;
+        }
+
+        // the whole drag is a single step of the history
+        if self.state == State::Dragging {
+            match event {
+                egui::Event::PointerMoved(pos) => {
+                    let dragged = self.nodes().find(|node| node.is_dragging());
+                    if let Some(delta) = dragged.map(|node| *pos - node.drag_start()) {
+                        self.drag_delta += delta;
+                    }
+                }
+                egui::Event::PointerButton {
+                    button: egui::PointerButton::Primary,
+                    pressed: false,
+                    ..
+                } => {
+                    let ids = self
+                        .nodes()
+                        .filter(|node| node.is_dragging())
+                        .map(|node| node.id())
+                        .collect();
+                    if self.drag_delta != egui::Vec2::ZERO {
+                        self.history.push(Edit::MoveNodes {
+                            ids,
+                            delta: self.drag_delta,
+                        });
+                    }
+                    self.drag_delta = egui::Vec2::ZERO;
+                }
+                _ => {}
+            }
+        }
+
+        let mut new_edge = None;
+        let mut moved = false;
         // TODO: Refactor to not loop over the nodes at the begginning
         // every time we clicck, it clicks two times => draw 2 nodes for nothing
-        for node in self.nodes.iter_mut() {
+        for ModelNode {
+            pos: model_pos,
+            data: node,
+            ..
+        } in self.model.nodes_mut()
+        {
             // move around the circle with the left click
             // the right click to choose the first node then the second the same way
             // it makes a vertex between them if they are not already connected
@@ -209,34 +675,23 @@ impl Graph {
 
             match self.state {
                 State::Idle => match event {
-                    egui::Event::PointerButton {
-                        pos,
-                        button: egui::PointerButton::Primary,
-                        pressed: true,
-                        ..
-                    } => {
-                        if node.contains(*pos) {
-                            node.set_is_dragging(true);
-                            node.set_drag_start(*pos);
-                            self.state = State::Dragging;
-                        }
-                    }
                     egui::Event::PointerButton {
                         pos,
                         button: egui::PointerButton::Secondary,
                         pressed: true,
                         ..
                     } => {
-                        if node.contains(*pos) {
+                        if node.contains((*model_pos).into(), *pos) {
                             // self.is_right_clicked = true;
                             node.set_drag_start(*pos);
                             self.state = State::RightClicked;
 
                             // Select the first node
                             assert!(self.new_vertex.node_selected_id1.is_none());
-                            self.new_vertex.select_first(node.to_owned());
+                            self.new_vertex.select_first(node.id(), (*model_pos).into());
                             assert!(self.new_vertex.node_selected_id1.is_some());
-                            self.new_vertex.select_second(node.to_owned());
+                            self.new_vertex
+                                .select_second(node.id(), (*model_pos).into());
                             assert!(self.new_vertex.node_selected_id2.is_some());
                         }
                     }
@@ -246,8 +701,9 @@ impl Graph {
                     egui::Event::PointerMoved(pos) => {
                         if node.is_dragging() {
                             let delta = *pos - node.drag_start();
-                            node.set_pos(node.pos() + delta);
+                            *model_pos = (model_pos.0 + delta.x, model_pos.1 + delta.y);
                             node.set_drag_start(*pos);
+                            moved = true;
                         }
                     }
                     egui::Event::PointerButton {
@@ -269,24 +725,18 @@ impl Graph {
                         pressed: true,
                         ..
                     } => {
-                        if node.contains(*pos) {
+                        if node.contains((*model_pos).into(), *pos) {
                             self.state = State::Idle;
 
                             assert!(self.new_vertex.node_selected_id1.is_some());
-                            self.new_vertex.select_second(node.to_owned());
-
-                            // add the edge
-                            // Cannot call add_edge because of borrowing
-                            // REFACTORING
-                            // TODO use the function add_edge rewrite it
-                            // self.nodes;
+                            self.new_vertex
+                                .select_second(node.id(), (*model_pos).into());
 
-                            Graph::add_rm_edge_(
+                            // the edge is added after the loop over the nodes
+                            new_edge = Some((
                                 self.new_vertex.node_selected_id1.unwrap(),
                                 self.new_vertex.node_selected_id2.unwrap(),
-                                &mut self.adjacencies,
-                                &mut self.vertices,
-                            );
+                            ));
 
                             // reset the new vertex
                             self.new_vertex = TemporaryVertex::default();
@@ -298,25 +748,126 @@ impl Graph {
             }
         }
 
-        // Update new vertex
-        if let Some(node2) = self.new_vertex.first_mut() {
-            node2.follow_mouse(event);
+        if moved {
+            self.history.touch();
         }
+        if let Some((id1, id2)) = new_edge {
+            self.add_rm_edge(id1, id2);
+        }
+
+        // Delete the node under the mouse, or else the selected nodes or vertex
+        if let egui::Event::Key {
+            key: egui::Key::Delete,
+            pressed: true,
+            ..
+        } = event
+        {
+            match self.pointer_pos().and_then(|pos| self.node_at(pos)) {
+                Some(id) if !self.selected_nodes.contains(&id) => self.rm_node(id),
+                _ if !self.selected_nodes.is_empty() => self.rm_selected_nodes(),
+                _ => {
+                    if let Some((id1, id2)) = self.selected_vertex {
+                        self.rm_edge(id1, id2);
+                    }
+                }
+            }
+        }
+
+        // Update new vertex
+        self.new_vertex.follow_mouse(event);
         // Update new node
-        if let Some(node) = self.new_node.as_mut() {
-            node.follow_mouse(event);
+        if let Some((_, node_pos)) = self.new_node.as_mut() {
+            if let egui::Event::PointerMoved(pos) = event {
+                *node_pos = *pos;
+            }
             if let egui::Event::Key {
                 key: egui::Key::A, ..
             } = event
             {
-                self.add_node(
-                    self.new_node.unwrap().pos(),
-                    egui::Stroke::new(2.0, egui::Color32::GREEN),
-                );
+                let pos = *node_pos;
+                self.add_node(pos, egui::Stroke::new(2.0, egui::Color32::GREEN));
             }
         }
     }
 
+    /// Select nodes with the left click, shift to add or remove one node from the selection,
+    /// drag the selection or draw a rectangle on the empty canvas to select the nodes inside
+    /// returns true if the event was used
+    fn update_selection(&mut self, event: &egui::Event) -> bool {
+        match (&self.state, event) {
+            (
+                State::Idle,
+                egui::Event::PointerButton {
+                    pos,
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    modifiers,
+                },
+            ) => {
+                match self.node_at(*pos) {
+                    Some(id) if modifiers.shift => {
+                        if !self.selected_nodes.remove(&id) {
+                            self.selected_nodes.insert(id);
+                        }
+                    }
+                    Some(id) => {
+                        // a node outside of the selection is dragged alone
+                        if !self.selected_nodes.contains(&id) {
+                            self.selected_nodes = HashSet::from([id]);
+                        }
+                        for node in self.model.nodes_mut() {
+                            if self.selected_nodes.contains(&node.id) {
+                                node.data.set_is_dragging(true);
+                                node.data.set_drag_start(*pos);
+                            }
+                        }
+                        self.state = State::Dragging;
+                    }
+                    None => {
+                        if !modifiers.shift {
+                            self.selected_nodes.clear();
+                        }
+                        self.selection_rect = Some((*pos, *pos));
+                        self.state = State::Selecting;
+                    }
+                }
+                true
+            }
+            (State::Selecting, egui::Event::PointerMoved(pos)) => {
+                if let Some((_, corner)) = self.selection_rect.as_mut() {
+                    *corner = *pos;
+                }
+                true
+            }
+            (
+                State::Selecting,
+                egui::Event::PointerButton {
+                    button: egui::PointerButton::Primary,
+                    pressed: false,
+                    ..
+                },
+            ) => {
+                if let Some((corner1, corner2)) = self.selection_rect.take() {
+                    let rect = egui::Rect::from_two_pos(corner1, corner2);
+                    for node in self.model.nodes() {
+                        if rect.contains(node.pos.into()) {
+                            self.selected_nodes.insert(node.id);
+                        }
+                    }
+                }
+                self.state = State::Idle;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the camera with the wheel, the middle button or space and the left button
+    /// returns true if the event was used by the camera
+    pub fn update_camera(&mut self, event: &egui::Event) -> bool {
+        self.camera.update(event)
+    }
+
     // UI ///////////////////////////////////////////////////////////////////
 
     /// Ui to add a new node
@@ -336,10 +887,9 @@ impl Graph {
     }
 
     pub fn begin_add_node_ui(&mut self) {
-        self.new_node = Some(Node::new_circle_node(
-            None,
+        self.new_node = Some((
+            Node::new_circle_node(None, egui::Stroke::new(2.0, egui::Color32::BLUE)),
             egui::Pos2::new(200.0, 400.0),
-            egui::Stroke::new(2.0, egui::Color32::BLUE),
         ));
         self.state = State::AddNode;
     }
@@ -352,7 +902,7 @@ impl Graph {
     /// Debug Text for the graph
     pub fn debug(&self, ui: &mut egui::Ui) {
         ui.label(format!("Current State: {}", self.state));
-        ui.label(format!("Number of nodes {}", self.nodes.len()));
+        ui.label(format!("Number of nodes {}", self.n_nodes()));
 
         // affiche les id des nodes de new_vertex
         ui.label(format!("New Vertex: {}", self.new_vertex,));
@@ -360,220 +910,581 @@ impl Graph {
         ui.label(format!("{self}"));
     }
 
-    /// Add a node at a certain position to the graph
-    pub fn add_node(&mut self, pos: egui::Pos2, stroke: egui::Stroke) {
-        self.nodes
-            .push(pos2_to_node(self.max_id, pos, Some(stroke)));
-        self.max_id += 1;
-        self.adjacencies.push(Vec::new());
+    /// Add a node at a certain position to the graph and return its id
+    pub fn add_node(&mut self, pos: egui::Pos2, stroke: egui::Stroke) -> usize {
+        let id = self.model.next_id();
+        self.do_edit(Edit::AddNode {
+            index: self.n_nodes(),
+            node: ModelNode {
+                id,
+                pos: (pos.x, pos.y),
+                data: Node::new_circle_node(Some(id), stroke),
+            },
+        });
+        id
+    }
+
+    /// Remove a node and every vertex that touches it
+    /// the other nodes keep their id
+    pub fn rm_node(&mut self, id: usize) {
+        let Some(index) = self.node_index(id) else {
+            return;
+        };
+        self.history.begin_group();
+        // from the last vertex so that the indices of the others do not change
+        for vertex_index in (0..self.n_vertices()).rev() {
+            let edge = &self.model.edges()[vertex_index];
+            if edge.id1 == id || edge.id2 == id {
+                self.do_edit(Edit::RemoveVertex {
+                    index: vertex_index,
+                    vertex: edge.clone(),
+                });
+            }
+        }
+        self.do_edit(Edit::RemoveNode {
+            index,
+            node: self.model.nodes()[index],
+        });
+        self.history.end_group();
+    }
+
+    /// Remove every selected node
+    pub fn rm_selected_nodes(&mut self) {
+        self.history.begin_group();
+        for id in self.selected_nodes() {
+            self.rm_node(id);
+        }
+        self.history.end_group();
+    }
+
+    /// Remove every node, this can be undone
+    pub fn clear(&mut self) {
+        self.history.begin_group();
+        for id in self.ids() {
+            self.rm_node(id);
+        }
+        self.history.end_group();
+    }
+
+    /// Add a vertex between every pair of selected nodes
+    pub fn connect_selected_nodes(&mut self) {
+        self.history.begin_group();
+        let ids = self.selected_nodes();
+        for (i, &id1) in ids.iter().enumerate() {
+            for &id2 in ids[i + 1..].iter() {
+                self.add_edge(id1, id2);
+            }
+        }
+        self.history.end_group();
+    }
+
+    /// Ids of every node, in order
+    fn ids(&self) -> Vec<usize> {
+        self.model.nodes().iter().map(|node| node.id).collect()
     }
 
     /// Add an edge between two nodes
-    /// borrow the graph (when you have ownership of the graph)
     pub fn add_edge(&mut self, id1: usize, id2: usize) {
-        Self::add_edge_(id1, id2, &mut self.adjacencies, &mut self.vertices);
+        if id1 != id2
+            && self.node_index(id1).is_some()
+            && self.node_index(id2).is_some()
+            && !self.model.neighbours(id1).contains(&id2)
+        {
+            self.do_edit(Edit::AddVertex {
+                index: self.n_vertices(),
+                vertex: ModelEdge {
+                    id1,
+                    id2,
+                    weight: None,
+                    data: Vertex::new(id1, id2, None),
+                },
+            });
+        }
     }
 
-    /// Add an edge between two nodes
-    /// borrow only the adjacencies and vertices
-    pub fn add_edge_(
-        id1: usize,
-        id2: usize,
-        adjacencies: &mut [Vec<usize>],
-        vertices: &mut Vec<Vertex>,
-    ) {
-        if !adjacencies[id1].contains(&id2) && !adjacencies[id2].contains(&id1) && id1 != id2 {
-            adjacencies[id1].push(id2);
-            adjacencies[id2].push(id1);
-            vertices.push(Vertex::new(id1, id2, None));
+    /// Graph with only the nodes `ids` and the vertices between them
+    pub fn subgraph(&self, ids: &[usize]) -> Graph {
+        let mut model = self.model.clone();
+        for id in self.ids() {
+            if !ids.contains(&id) {
+                model.rm_node(id);
+            }
+        }
+        Graph {
+            model,
+            camera: self.camera,
+            ..Default::default()
         }
     }
 
     /// Add an edge between two nodes, if the edge already exists, remove it
     pub fn add_rm_edge(&mut self, id1: usize, id2: usize) {
-        Self::add_rm_edge_(id1, id2, &mut self.adjacencies, &mut self.vertices);
-    }
-
-    pub fn add_rm_edge_(
-        id1: usize,
-        id2: usize,
-        adjacencies: &mut [Vec<usize>],
-        vertices: &mut Vec<Vertex>,
-    ) {
-        if adjacencies[id1].contains(&id2) && adjacencies[id2].contains(&id1) {
-            // remove the edge
-            Self::rm_edge_(id1, id2, adjacencies, vertices);
+        if self.vertex_index(id1, id2).is_some() {
+            self.rm_edge(id1, id2);
         } else {
-            // add the edge
-            adjacencies[id1].push(id2);
-            adjacencies[id2].push(id1);
-            vertices.push(Vertex::new(id1, id2, None));
+            self.add_edge(id1, id2);
         }
     }
 
     // add every edge possible to the graph
     pub fn add_every_edge(&mut self) {
-        for i in 0..self.nodes.len() {
-            for j in 0..self.nodes.len() {
+        self.history.begin_group();
+        let ids = self.ids();
+        for &i in ids.iter() {
+            for &j in ids.iter() {
                 if i != j {
                     self.add_edge(i, j);
                 }
             }
         }
+        self.history.end_group();
     }
 
-    pub fn rm_edge(&mut self, id1: usize, id2: usize) {
-        self.adjacencies[id1].retain(|&x| x != id2);
-        self.adjacencies[id2].retain(|&x| x != id1);
-        self.vertices.retain(|x| {
-            (x.node_id1() != id1 || x.node_id2() != id2)
-                & (x.node_id1() != id2 || x.node_id2() != id1)
-        });
+    /// Add the edges of the Euclidean minimum spanning tree of the nodes, as a single edit
+    /// it is the covering tree of `add_every_edge` with the geometric weighting
+    pub fn connect_euclidean_mst(&mut self) {
+        self.add_edges(self.model.euclidean_mst());
     }
 
-    pub fn rm_edge_(
-        id1: usize,
-        id2: usize,
-        adjacencies: &mut [Vec<usize>],
-        vertices: &mut Vec<Vertex>,
-    ) {
-        adjacencies[id1].retain(|&x| x != id2);
-        adjacencies[id2].retain(|&x| x != id1);
-        vertices.retain(|x| {
-            (x.node_id1() != id1 || x.node_id2() != id2)
-                & (x.node_id1() != id2 || x.node_id2() != id1)
-        });
+    /// Add the edges of a proximity graph of the nodes, as a single edit
+    pub fn connect_proximity(&mut self, proximity_graph: ProximityGraph) {
+        self.add_edges(self.model.proximity_edges(proximity_graph));
     }
 
-    /// Checks if the graph has a cycle
-    pub fn has_cycle(&self) -> bool {
-        for i in 0..self.nodes.len() {
-            if self.has_cycle_from(i) {
-                return true;
+    /// Add the nodes and the edges of another graph, as a single edit,
+    /// returns the new ids of its nodes in its order
+    pub fn add_graph(&mut self, other: &Graph, node_stroke: egui::Stroke) -> Vec<usize> {
+        self.history.begin_group();
+        let ids: Vec<usize> = other
+            .model
+            .nodes()
+            .iter()
+            .map(|node| self.add_node(node.pos.into(), node_stroke))
+            .collect();
+        for edge in other.model.edges() {
+            let (Some(index1), Some(index2)) =
+                (other.node_index(edge.id1), other.node_index(edge.id2))
+            else {
+                continue;
+            };
+            self.add_edge(ids[index1], ids[index2]);
+        }
+        self.history.end_group();
+        ids
+    }
+
+    /// Ids of the nodes with the positions where a layout places them,
+    /// an error if the root of a tree layout is not a node of the graph
+    pub fn layout_positions(
+        &self,
+        layout: Layout,
+        spacing: f32,
+    ) -> Result<Vec<(usize, egui::Pos2)>, String> {
+        Ok(self
+            .model
+            .layout(layout, spacing)?
+            .into_iter()
+            .map(|(id, (x, y))| (id, egui::Pos2::new(x, y)))
+            .collect())
+    }
+
+    /// Move the nodes where a layout places them, as a single edit
+    pub fn apply_layout(&mut self, layout: Layout, spacing: f32) -> Result<(), String> {
+        self.set_node_positions(&self.layout_positions(layout, spacing)?);
+        Ok(())
+    }
+
+    /// Move every node to its position, as a single edit
+    pub fn set_node_positions(&mut self, positions: &[(usize, egui::Pos2)]) {
+        self.history.begin_group();
+        for &(id, pos) in positions {
+            let Some(before) = self.model.pos(id) else {
+                continue;
+            };
+            let delta = pos - egui::Pos2::from(before);
+            if delta != egui::Vec2::ZERO {
+                self.do_edit(Edit::MoveNodes {
+                    ids: vec![id],
+                    delta,
+                });
             }
         }
-        false
+        self.history.end_group();
     }
 
-    pub fn has_cycle_from(&self, start: usize) -> bool {
-        let mut visited = vec![false; self.nodes.len()];
-        let mut queue = vec![(start, start)];
-        while let Some((last, current)) = queue.pop() {
-            if visited[current] {
-                // Already visited the node => there is a cycle
-                return true;
-            } else {
-                // Mark the current node visited
-                visited[current] = true;
-                // Add its neighbours to the queue with the exception of the node we came from
-                for &adj in self.adjacencies[current].iter() {
-                    if adj != last {
-                        queue.push((current, adj));
+    /// Move every node to its position without recording it in the history,
+    /// for the frames of an animation
+    pub fn preview_node_positions(&mut self, positions: &[(usize, egui::Pos2)]) {
+        for &(id, pos) in positions {
+            self.model.set_pos(id, (pos.x, pos.y));
+        }
+        // the weights may have changed
+        self.history.touch();
+    }
+
+    fn add_edges(&mut self, edges: Vec<(usize, usize)>) {
+        self.history.begin_group();
+        for (id1, id2) in edges {
+            self.add_edge(id1, id2);
+        }
+        self.history.end_group();
+    }
+
+    pub fn rm_edge(&mut self, id1: usize, id2: usize) {
+        if let Some(index) = self.vertex_index(id1, id2) {
+            self.do_edit(Edit::RemoveVertex {
+                index,
+                vertex: self.model.edges()[index].clone(),
+            });
+        }
+    }
+
+    // HISTORY /////////////////////////////////////////////////////////////////////
+    /// Changed by every change of the nodes, the vertices or their weights,
+    /// also by the moves of a drag or an animation that are not recorded yet
+    pub fn revision(&self) -> Revision {
+        self.history.revision()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Cancel the last edit of the graph
+    pub fn undo(&mut self) {
+        if let Some(edit) = self.history.undo() {
+            self.apply(edit.inverse());
+        }
+    }
+
+    /// Do the last undone edit again
+    pub fn redo(&mut self) {
+        if let Some(edit) = self.history.redo() {
+            self.apply(edit);
+        }
+    }
+
+    /// Forget every edit, they cannot be undone anymore
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Apply an edit and record it in the history
+    fn do_edit(&mut self, edit: Edit) {
+        self.apply(edit.clone());
+        self.history.push(edit);
+    }
+
+    /// Change the way the node at `index` is drawn and record it in the history
+    fn change_node(&mut self, index: usize, change: impl FnOnce(&mut Node)) {
+        let before = self.model.nodes()[index].data;
+        let mut after = before;
+        change(&mut after);
+        self.do_edit(Edit::ChangeNode { before, after });
+    }
+
+    /// Change the way the vertex at `index` is drawn and record it in the history
+    fn change_vertex(&mut self, index: usize, change: impl FnOnce(&mut Vertex)) {
+        let before = self.model.edges()[index].data.clone();
+        let mut after = before.clone();
+        change(&mut after);
+        self.do_edit(Edit::ChangeVertex { before, after });
+    }
+
+    /// Apply an edit without recording it
+    fn apply(&mut self, edit: Edit) {
+        match edit {
+            Edit::AddNode { index, mut node } => {
+                // the node may have been removed while it was dragged
+                node.data.set_is_dragging(false);
+                self.model.insert_node(index, node);
+            }
+            Edit::RemoveNode { node, .. } => {
+                self.model.rm_node(node.id);
+                self.forget_removed();
+            }
+            Edit::MoveNodes { ids, delta } => {
+                for id in ids {
+                    if let Some((x, y)) = self.model.pos(id) {
+                        self.model.set_pos(id, (x + delta.x, y + delta.y));
                     }
                 }
             }
+            Edit::AddVertex { index, vertex } => {
+                self.model.insert_edge(index, vertex);
+            }
+            Edit::RemoveVertex { vertex, .. } => {
+                self.model.rm_edge(vertex.id1, vertex.id2);
+                self.forget_removed();
+            }
+            Edit::SetWeight {
+                id1, id2, after, ..
+            } => {
+                self.model.set_weight(id1, id2, after);
+            }
+            Edit::ChangeNode { after, .. } => {
+                if let Some(node) = self.model.node_data_mut(after.id()) {
+                    *node = after;
+                }
+            }
+            Edit::ChangeVertex { after, .. } => {
+                let (id1, id2) = (after.node_id1(), after.node_id2());
+                if let Some(vertex) = self.model.edge_data_mut(id1, id2) {
+                    *vertex = after;
+                }
+            }
+            Edit::Group(edits) => {
+                for edit in edits {
+                    self.apply(edit);
+                }
+            }
+        }
+    }
+
+    /// Forget the selections, the new vertex and the drag of removed nodes and vertices
+    fn forget_removed(&mut self) {
+        let model = &self.model;
+        self.selected_nodes
+            .retain(|&id| model.node_index(id).is_some());
+        self.set_selected_vertex(self.selected_vertex);
+
+        let new_vertex = [
+            self.new_vertex.node_selected_id1,
+            self.new_vertex.node_selected_id2,
+        ];
+        if new_vertex
+            .iter()
+            .flatten()
+            .any(|&id| self.node_index(id).is_none())
+        {
+            self.new_vertex = TemporaryVertex::default();
+            self.state = State::Idle;
+        }
+        if self.state == State::Dragging && !self.nodes().any(|node| node.is_dragging()) {
+            self.state = State::Idle;
         }
-        false
+    }
+
+    /// Checks if the graph has a cycle
+    pub fn has_cycle(&self) -> bool {
+        self.model.has_cycle()
+    }
+
+    pub fn has_cycle_from(&self, start: usize) -> bool {
+        self.model.has_cycle_from(start)
+    }
+
+    /// Every vertex of the graph as the ids of its nodes and its weight
+    /// in the same order as the vertices
+    pub fn weighted_edges(&self) -> Vec<WeightedEdge> {
+        self.model.weighted_edges()
+    }
+
+    /// `weighted_edges` with the nodes given by their index in `nodes`,
+    /// as expected by the spanning tree algorithms
+    pub fn indexed_edges(&self) -> Vec<WeightedEdge> {
+        self.model.indexed_edges()
     }
 
     // function that calculates the minimum covering tree
     // returns a graph with the same nodes
     // but only the edges that are in the tree
-    pub fn covering_tree(&self, min_covering_tree_algo: bool) -> Graph {
-        let mut graph = self.graph_without_edges();
-        let mut vertices_ordered = Vec::<(usize, usize, f32)>::new();
-
-        // we add every vertex of the graph in the vertex vector
-        for vertex in self.vertices.iter() {
-            vertices_ordered.push((
-                vertex.node_id1(),
-                vertex.node_id2(),
-                self.nodes[vertex.node_id1()]
-                    .pos()
-                    .distance(self.nodes[vertex.node_id2()].pos()),
-            ));
-        }
-
-        // we order the vertex vector by the distance between the nodes
-        if min_covering_tree_algo {
-            vertices_ordered
-                .sort_by(|(_, _, w1), (_, _, w2)| w1.partial_cmp(w2).unwrap().reverse());
-        } else {
-            vertices_ordered.sort_by(|(_, _, w1), (_, _, w2)| w1.partial_cmp(w2).unwrap());
+    pub fn covering_tree(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        min_covering_tree_algo: bool,
+    ) -> Graph {
+        // the vertices of the tree are drawn with the default style
+        let model = self
+            .model
+            .covering_tree(algorithm, min_covering_tree_algo)
+            .map(
+                |node| node,
+                |vertex| Vertex::new(vertex.node_id1(), vertex.node_id2(), None),
+            );
+        Graph {
+            model,
+            camera: self.camera,
+            ..Default::default()
         }
+    }
 
-        // We add the edges in the graph in the order of the weight of every vertex
-        // if the edge doesn't create a cycle we don't add it
-        for (i, adj, _) in vertices_ordered {
-            graph.add_edge(i, adj);
-            if graph.has_cycle() {
-                graph.rm_edge(i, adj);
-            }
-        }
-        graph
+    /// Every step of the computation of the covering tree
+    /// the edge of a step is the index of a vertex of this graph
+    pub fn covering_tree_steps(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        min_covering_tree_algo: bool,
+    ) -> Vec<Step> {
+        self.model
+            .covering_tree_steps(algorithm, min_covering_tree_algo)
     }
 
-    pub fn graph_without_edges(&self) -> Graph {
-        let Graph { nodes, max_id, .. } = self;
-        Graph {
-            nodes: nodes.to_vec(),
-            adjacencies: vec![Vec::new(); nodes.len()],
-            max_id: *max_id,
-            vertices: Vec::new(),
+    /// Shortest path between two nodes, its edges are indices of vertices of this graph
+    pub fn shortest_path(
+        &self,
+        algorithm: ShortestPathAlgorithm,
+        source: usize,
+        destination: usize,
+    ) -> Option<Path> {
+        self.model.shortest_path(algorithm, source, destination)
+    }
 
-            new_vertex: TemporaryVertex::default(),
+    /// Minimum (or maximum) spanning forest, its edges are indices of vertices of this graph
+    pub fn spanning_forest(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        min_covering_tree_algo: bool,
+    ) -> SpanningForest {
+        self.model
+            .spanning_forest(algorithm, min_covering_tree_algo)
+    }
 
-            state: State::Idle,
+    /// Connected components, labeled by the index of the nodes in `nodes`
+    pub fn components(&self) -> Components {
+        self.model.components()
+    }
 
-            new_node: None,
-        }
+    /// Bridges (indices of vertices) and articulation points (ids) of the graph
+    pub fn critical_parts(&self) -> CriticalParts {
+        self.model.critical_parts()
     }
 
-    pub fn pos2_to_graph(
-        positions: Vec<egui::Pos2>,
-        adjacencies: Vec<Vec<usize>>,
-        node_stroke: egui::Stroke,
-    ) -> Self {
-        let mut max_id: usize = 0;
-        let result = Self {
-            nodes: positions
+    /// Sum of the weights of every vertex of the graph
+    pub fn total_weight(&self) -> f32 {
+        self.model.total_weight()
+    }
+
+    /// The graph without the way it is drawn, the vertices are in the same order
+    pub fn to_model(&self) -> Model {
+        self.to_model_with(|_| (), |_| ())
+    }
+
+    /// `to_model` with data computed from every node and every vertex,
+    /// `to_model_with(Node::clone, Vertex::clone)` keeps the way they are drawn
+    pub fn to_model_with<N, E>(
+        &self,
+        mut node_data: impl FnMut(&Node) -> N,
+        mut vertex_data: impl FnMut(&Vertex) -> E,
+    ) -> Model<N, E> {
+        Model::from_parts(
+            self.model
+                .nodes()
+                .iter()
+                .map(|node| ModelNode {
+                    id: node.id,
+                    pos: node.pos,
+                    data: node_data(&node.data),
+                })
+                .collect(),
+            self.model.adjacencies().to_vec(),
+            self.model
+                .edges()
                 .iter()
-                .map(|&pos| {
-                    let result = pos2_to_node(max_id, pos, Some(node_stroke));
-                    max_id += 1;
-                    result
+                .map(|edge| ModelEdge {
+                    id1: edge.id1,
+                    id2: edge.id2,
+                    weight: edge.weight,
+                    data: vertex_data(&edge.data),
                 })
                 .collect(),
-            adjacencies,
-            max_id: positions.len(),
-            vertices: Vec::new(),
+            self.weighting(),
+        )
+    }
 
-            state: State::default(),
+    // SAVE ////////////////////////////////////////////////////////////////////////
+    pub fn to_data(&self, textures: &(impl TextureNames + ?Sized)) -> GraphData {
+        GraphData {
+            nodes: self
+                .model
+                .nodes()
+                .iter()
+                .map(|node| node.data.to_data(node.pos, textures))
+                .collect(),
+            adjacencies: self.model.adjacencies().to_vec(),
+            vertices: self
+                .model
+                .edges()
+                .iter()
+                .map(|edge| edge.data.to_data(edge.weight, textures))
+                .collect(),
+            weighting: self.weighting(),
+        }
+    }
 
-            new_vertex: TemporaryVertex::default(),
+    /// Graph saved in `data`, which must be valid (see `GraphData::from_json`)
+    pub fn from_data(data: GraphData, textures: &(impl TextureNames + ?Sized)) -> Graph {
+        let nodes = data
+            .nodes
+            .iter()
+            .map(|node| ModelNode {
+                id: node.id,
+                pos: (node.pos[0], node.pos[1]),
+                data: Node::from_data(node, textures),
+            })
+            .collect();
+        let edges = data
+            .vertices
+            .iter()
+            .map(|vertex| ModelEdge {
+                id1: vertex.node_id1,
+                id2: vertex.node_id2,
+                weight: vertex.weight,
+                data: Vertex::from_data(vertex, textures),
+            })
+            .collect();
+        Graph {
+            model: Model::from_parts(nodes, data.adjacencies, edges, data.weighting),
+            ..Default::default()
+        }
+    }
 
-            new_node: None,
-        };
-        if result.adjacencies.len() != positions.len() {
-            println!("{result}");
+    pub fn graph_without_edges(&self) -> Graph {
+        Graph {
+            model: self.model.subgraph_of_edges(&[]),
+            camera: self.camera,
+            ..Default::default()
+        }
+    }
+
+    /// Graph with a node at every position, `adjacencies[i]` are the neighbours of the i-th one
+    pub fn pos2_to_graph(
+        positions: Vec<egui::Pos2>,
+        adjacencies: Vec<Vec<usize>>,
+        node_stroke: egui::Stroke,
+    ) -> Self {
+        if adjacencies.len() != positions.len() {
             panic!("The number of nodes and the number of adjacency lists must be the same");
         }
-        result
+        let nodes = positions
+            .iter()
+            .enumerate()
+            .map(|(id, &pos)| ModelNode {
+                id,
+                pos: (pos.x, pos.y),
+                data: Node::new_circle_node(Some(id), node_stroke),
+            })
+            .collect();
+        Graph::new(nodes, adjacencies)
     }
 }
 
 impl std::fmt::Display for Graph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Nodes: ")?;
-        for node in self.nodes.iter() {
+        for node in self.nodes() {
             write!(f, "| {node} |")?;
         }
         writeln!(f)?;
 
         writeln!(f, "Adjacencies:")?;
 
-        for (i, adj) in self.adjacencies.iter().enumerate() {
-            write!(f, "\t {i} -> ")?;
+        for (node, adj) in self.model.nodes().iter().zip(self.model.adjacencies()) {
+            write!(f, "\t {} -> ", node.id)?;
             for j in adj.iter() {
                 write!(f, "| {j} |")?;
             }
@@ -581,11 +1492,172 @@ impl std::fmt::Display for Graph {
         }
 
         write!(f, "Vertices:")?;
-        for vertex in self.vertices.iter() {
+        for vertex in self.vertices() {
             write!(f, "\t {vertex}")?;
         }
         writeln!(f)?;
-        writeln!(f, "Max id: {}", self.max_id)?;
+        writeln!(f, "Max id: {}", self.model.next_id())?;
+        writeln!(f, "Weighting: {}", self.weighting())?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(graph: &Graph) -> Vec<(usize, usize, Option<f32>)> {
+        graph
+            .model()
+            .edges()
+            .iter()
+            .map(|edge| (edge.id1, edge.id2, edge.weight))
+            .collect()
+    }
+
+    #[test]
+    fn edits_and_undo_change_the_model() {
+        let stroke = egui::Stroke::default();
+        let mut graph = Graph::default();
+        let ids: Vec<usize> = (0..4)
+            .map(|i| graph.add_node(egui::pos2(i as f32 * 10., 0.), stroke))
+            .collect();
+        graph.add_edge(ids[0], ids[1]);
+        graph.add_edge(ids[1], ids[2]);
+        graph.add_edge(ids[2], ids[3]);
+        graph.add_edge(ids[2], ids[3]);
+        graph.add_edge(ids[3], ids[3]);
+        graph.set_weight(ids[1], ids[2], Some(4.));
+        graph.set_node_positions(&[(ids[3], egui::pos2(20., 15.))]);
+        assert_eq!(graph.n_vertices(), 3);
+        assert_eq!(graph.distance(ids[2], ids[3]), Some(15.));
+        assert_eq!(graph.explicit_weight(ids[1], ids[2]), Some(4.));
+
+        let before = edges(&graph);
+        let positions = graph.node_positions();
+        graph.rm_node(ids[1]);
+        assert_eq!(graph.n_nodes(), 3);
+        assert_eq!(edges(&graph), vec![(ids[2], ids[3], None)]);
+        assert_eq!(graph.distance(ids[0], ids[1]), None);
+
+        graph.undo();
+        assert_eq!(edges(&graph), before);
+        assert_eq!(graph.node_positions(), positions);
+
+        graph.undo();
+        graph.undo();
+        assert_eq!(graph.explicit_weight(ids[1], ids[2]), None);
+        assert_eq!(graph.distance(ids[2], ids[3]), Some(10.));
+
+        graph.redo();
+        graph.redo();
+        assert_eq!(edges(&graph), before);
+        assert_eq!(graph.node_positions(), positions);
+
+        // the next node does not reuse the id of the removed one
+        graph.rm_node(ids[3]);
+        graph.undo();
+        graph.rm_node(ids[3]);
+        assert_eq!(graph.add_node(egui::Pos2::ZERO, stroke), ids[3] + 1);
+    }
+
+    #[test]
+    fn every_change_gives_a_new_revision() {
+        let mut graph = Graph::default();
+        let mut revisions = vec![graph.revision()];
+        let mut changed = |graph: &Graph| {
+            assert!(!revisions.contains(&graph.revision()));
+            revisions.push(graph.revision());
+        };
+        let id1 = graph.add_node(egui::pos2(0., 0.), egui::Stroke::default());
+        changed(&graph);
+        let id2 = graph.add_node(egui::pos2(10., 0.), egui::Stroke::default());
+        changed(&graph);
+        graph.add_edge(id1, id2);
+        changed(&graph);
+        graph.set_weight(id1, id2, Some(2.));
+        changed(&graph);
+        graph.set_weighting(Weighting::Explicit);
+        changed(&graph);
+        graph.undo();
+        changed(&graph);
+        graph.redo();
+        changed(&graph);
+
+        // what does not change the vertices or their weights keeps the revision
+        let revision = graph.revision();
+        graph.set_highlight_vertex(0, Highlight::Accepted);
+        graph.set_weighting(Weighting::Explicit);
+        graph.redo();
+        graph.clear_highlights();
+        assert_eq!(graph.revision(), revision);
+        assert_ne!(Graph::default().revision(), revision);
+    }
+
+    #[test]
+    fn a_weight_drag_is_a_single_step() {
+        let mut graph = Graph::default();
+        let id1 = graph.add_node(egui::pos2(0., 0.), egui::Stroke::default());
+        let id2 = graph.add_node(egui::pos2(10., 0.), egui::Stroke::default());
+        graph.add_edge(id1, id2);
+        graph.set_weighting(Weighting::Explicit);
+
+        graph.set_weight(id1, id2, Some(1.));
+        graph.set_weight(id1, id2, Some(2.));
+        for weight in 3..6 {
+            graph.drag_weight(id1, id2, Some(weight as f32));
+        }
+        graph.end_weight_drag();
+        for weight in 6..9 {
+            graph.drag_weight(id1, id2, Some(weight as f32));
+        }
+        assert_eq!(graph.explicit_weight(id1, id2), Some(8.));
+        graph.set_weight(id1, id2, Some(9.));
+        graph.drag_weight(id1, id2, Some(10.));
+
+        for weight in [Some(9.), Some(8.), Some(5.), Some(2.), Some(1.), None] {
+            graph.undo();
+            assert_eq!(graph.explicit_weight(id1, id2), weight);
+            assert_eq!(graph.model().weight(id1, id2), Some(weight.unwrap_or(10.)));
+        }
+        graph.redo();
+        graph.redo();
+        graph.redo();
+        graph.drag_weight(id1, id2, Some(4.));
+        graph.undo();
+        assert_eq!(graph.explicit_weight(id1, id2), Some(5.));
+        // the drag replaced the undone steps
+        graph.redo();
+        assert_eq!(graph.explicit_weight(id1, id2), Some(4.));
+        assert!(!graph.can_redo());
+    }
+
+    #[test]
+    fn the_covering_tree_keeps_the_nodes_and_weights() {
+        let stroke = egui::Stroke::default();
+        let mut graph = Graph::default();
+        let ids: Vec<usize> = [(0., 0.), (30., 0.), (0., 40.)]
+            .into_iter()
+            .map(|(x, y)| graph.add_node(egui::pos2(x, y), stroke))
+            .collect();
+        graph.add_every_edge();
+        graph.set_weighting(Weighting::Explicit);
+        graph.set_weight(ids[1], ids[2], Some(1.));
+        let tree = graph.covering_tree(SpanningTreeAlgorithm::Kruskal, false);
+        assert_eq!(tree.n_nodes(), 3);
+        assert_eq!(tree.n_vertices(), 2);
+        assert_eq!(tree.explicit_weight(ids[1], ids[2]), Some(1.));
+        assert_eq!(tree.total_weight(), 31.);
+        assert_eq!(tree.node_positions(), graph.node_positions());
+        assert_eq!(
+            graph.graph_without_edges().node_positions(),
+            graph.node_positions()
+        );
+        let subgraph = graph.subgraph(&[ids[0], ids[2]]);
+        assert_eq!(
+            subgraph.node_positions(),
+            vec![(ids[0], egui::pos2(0., 0.)), (ids[2], egui::pos2(0., 40.))]
+        );
+        assert_eq!(subgraph.n_vertices(), 1);
+    }
+}
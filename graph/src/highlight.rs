@@ -0,0 +1,57 @@
+/// Highlight drawn on top of a vertex or a node
+/// to show the state of an algorithm without rebuilding the graph
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Highlight {
+    #[default]
+    None,
+    Considered,
+    Accepted,
+    Rejected,
+    /// Part of a path between two nodes
+    Path,
+    /// Part of the connected component with this number
+    Component(usize),
+    /// Bridge or articulation point, the graph is split without it
+    Critical,
+}
+
+/// Colours of the components, used again when there are more components
+const COMPONENT_COLORS: [egui::Color32; 8] = [
+    egui::Color32::from_rgb(230, 25, 75),
+    egui::Color32::from_rgb(60, 180, 75),
+    egui::Color32::from_rgb(0, 130, 200),
+    egui::Color32::from_rgb(245, 130, 48),
+    egui::Color32::from_rgb(145, 30, 180),
+    egui::Color32::from_rgb(70, 240, 240),
+    egui::Color32::from_rgb(240, 50, 230),
+    egui::Color32::from_rgb(210, 245, 60),
+];
+
+impl Highlight {
+    pub fn color(&self) -> Option<egui::Color32> {
+        match self {
+            Highlight::None => None,
+            Highlight::Considered => Some(egui::Color32::YELLOW),
+            Highlight::Accepted => Some(egui::Color32::from_rgb(0, 200, 255)),
+            Highlight::Rejected => Some(egui::Color32::RED),
+            Highlight::Path => Some(egui::Color32::from_rgb(255, 140, 0)),
+            Highlight::Component(label) => Some(COMPONENT_COLORS[label % COMPONENT_COLORS.len()]),
+            Highlight::Critical => Some(egui::Color32::from_rgb(200, 0, 120)),
+        }
+    }
+}
+
+impl std::fmt::Display for Highlight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Highlight::None => write!(f, "None")?,
+            Highlight::Considered => write!(f, "Considered")?,
+            Highlight::Accepted => write!(f, "Accepted")?,
+            Highlight::Rejected => write!(f, "Rejected")?,
+            Highlight::Path => write!(f, "Path")?,
+            Highlight::Component(label) => write!(f, "Component {label}")?,
+            Highlight::Critical => write!(f, "Critical")?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use crate::model::{ModelEdge, ModelNode};
+use crate::node::Node;
+use crate::vertex::Vertex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// One reversible change of a graph
+#[derive(Debug, Clone)]
+pub(crate) enum Edit {
+    AddNode {
+        index: usize,
+        node: ModelNode<Node>,
+    },
+    /// The vertices of the node are removed before it
+    RemoveNode {
+        index: usize,
+        node: ModelNode<Node>,
+    },
+    MoveNodes {
+        ids: Vec<usize>,
+        delta: egui::Vec2,
+    },
+    AddVertex {
+        index: usize,
+        vertex: ModelEdge<Vertex>,
+    },
+    RemoveVertex {
+        index: usize,
+        vertex: ModelEdge<Vertex>,
+    },
+    SetWeight {
+        id1: usize,
+        id2: usize,
+        before: Option<f32>,
+        after: Option<f32>,
+    },
+    /// Change of the way a node is drawn
+    ChangeNode {
+        before: Node,
+        after: Node,
+    },
+    /// Change of the way a vertex is drawn
+    ChangeVertex {
+        before: Vertex,
+        after: Vertex,
+    },
+    /// Edits undone and redone together
+    Group(Vec<Edit>),
+}
+
+impl Edit {
+    /// The edit that cancels this one
+    pub(crate) fn inverse(self) -> Edit {
+        match self {
+            Edit::AddNode { index, node } => Edit::RemoveNode { index, node },
+            Edit::RemoveNode { index, node } => Edit::AddNode { index, node },
+            Edit::MoveNodes { ids, delta } => Edit::MoveNodes { ids, delta: -delta },
+            Edit::AddVertex { index, vertex } => Edit::RemoveVertex { index, vertex },
+            Edit::RemoveVertex { index, vertex } => Edit::AddVertex { index, vertex },
+            Edit::SetWeight {
+                id1,
+                id2,
+                before,
+                after,
+            } => Edit::SetWeight {
+                id1,
+                id2,
+                before: after,
+                after: before,
+            },
+            Edit::ChangeNode { before, after } => Edit::ChangeNode {
+                before: after,
+                after: before,
+            },
+            Edit::ChangeVertex { before, after } => Edit::ChangeVertex {
+                before: after,
+                after: before,
+            },
+            Edit::Group(edits) => Edit::Group(edits.into_iter().rev().map(Edit::inverse).collect()),
+        }
+    }
+}
+
+/// State of a graph, a new one is given after every change of the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Revision(u64);
+
+impl Default for Revision {
+    /// A revision no graph had before, so that two graphs never have the same
+    fn default() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Revision(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Edits of a graph that can be undone, and undone edits that can be redone
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    done: Vec<Edit>,
+    undone: Vec<Edit>,
+    /// Groups being recorded, the last one is the innermost
+    groups: Vec<Vec<Edit>>,
+    revision: Revision,
+    /// Nodes of the vertex whose weight is being dragged, the changes of its weight are
+    /// merged in the last edit until the drag ends
+    weight_drag: Option<(usize, usize)>,
+}
+
+impl History {
+    /// Revision of the graph, changed by every edit, undo and redo
+    pub fn revision(&self) -> Revision {
+        self.revision
+    }
+
+    /// Give a new revision to the graph, after a change that is not in the history
+    pub(crate) fn touch(&mut self) {
+        self.revision = Revision::default();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+        self.groups.clear();
+    }
+
+    /// Record an edit, the undone edits cannot be redone anymore
+    pub(crate) fn push(&mut self, edit: Edit) {
+        self.touch();
+        self.weight_drag = None;
+        if let Some(group) = self.groups.last_mut() {
+            group.push(edit);
+            return;
+        }
+        self.undone.clear();
+        self.done.push(edit);
+    }
+
+    /// Record a change of weight made by dragging a value, the changes of the same drag
+    /// are a single step, until `end_weight_drag`
+    pub(crate) fn push_weight_drag(&mut self, edit: Edit) {
+        if let (
+            Some(Edit::SetWeight {
+                id1, id2, after, ..
+            }),
+            Edit::SetWeight {
+                id1: new_id1,
+                id2: new_id2,
+                after: new_after,
+                ..
+            },
+        ) = (self.done.last_mut(), &edit)
+        {
+            if self.groups.is_empty()
+                && self.weight_drag == Some((*id1, *id2))
+                && (*id1, *id2) == (*new_id1, *new_id2)
+            {
+                *after = *new_after;
+                self.touch();
+                return;
+            }
+        }
+        let weight_drag = match edit {
+            Edit::SetWeight { id1, id2, .. } => Some((id1, id2)),
+            _ => None,
+        };
+        self.push(edit);
+        self.weight_drag = weight_drag;
+    }
+
+    /// The next change of weight is a new step
+    pub(crate) fn end_weight_drag(&mut self) {
+        self.weight_drag = None;
+    }
+
+    /// Record the next edits as a single one, until `end_group`
+    pub(crate) fn begin_group(&mut self) {
+        self.groups.push(Vec::new());
+    }
+
+    pub(crate) fn end_group(&mut self) {
+        if let Some(edits) = self.groups.pop() {
+            match edits.len() {
+                0 => {}
+                1 => self.push(edits.into_iter().next().unwrap()),
+                _ => self.push(Edit::Group(edits)),
+            }
+        }
+    }
+
+    /// Last edit done, to apply its inverse
+    pub(crate) fn undo(&mut self) -> Option<Edit> {
+        let edit = self.done.pop()?;
+        self.undone.push(edit.clone());
+        self.touch();
+        self.weight_drag = None;
+        Some(edit)
+    }
+
+    /// Last edit undone, to apply it again
+    pub(crate) fn redo(&mut self) -> Option<Edit> {
+        let edit = self.undone.pop()?;
+        self.done.push(edit.clone());
+        self.touch();
+        self.weight_drag = None;
+        Some(edit)
+    }
+}
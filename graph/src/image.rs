@@ -1,57 +1,42 @@
 // use eframe::epaint;
 
+/// Texture drawn centered on a position given when it is drawn
 #[derive(Debug, Clone, Copy)]
 pub struct Image {
-    pos: egui::Pos2,
     size: egui::Vec2,
     texture_id: egui::TextureId,
 }
 
 impl Image {
-    pub fn new(pos: egui::Pos2, size: egui::Vec2, texture_id: egui::TextureId) -> Self {
-        Self {
-            pos,
-            size,
-            texture_id,
-        }
+    pub fn new(size: egui::Vec2, texture_id: egui::TextureId) -> Self {
+        Self { size, texture_id }
     }
 
-    pub fn contains(&self, point: egui::Pos2) -> bool {
-        self.rect().contains(point)
-    }
-
-    pub fn follow_mouse(&mut self, event: &egui::Event) {
-        if let egui::Event::PointerMoved(pos) = event {
-            self.pos = *pos;
-        }
-    }
-
-    // SETTER //////////////////////////////////////////////////////////////////////
-    pub fn set_pos(&mut self, pos: egui::Pos2) {
-        self.pos = pos;
+    pub fn contains(&self, pos: egui::Pos2, point: egui::Pos2) -> bool {
+        self.rect(pos).contains(point)
     }
 
     // GETTER //////////////////////////////////////////////////////////////////////
-    pub fn pos(&self) -> egui::Pos2 {
-        self.pos
+    pub fn size(&self) -> egui::Vec2 {
+        self.size
     }
 
-    pub fn _center(&self) -> egui::Pos2 {
-        self.pos
+    pub fn texture_id(&self) -> egui::TextureId {
+        self.texture_id
     }
 
-    pub fn rect(&self) -> egui::Rect {
-        egui::Rect::from_min_size(self.pos - self.size / 2., self.size)
+    pub fn rect(&self, pos: egui::Pos2) -> egui::Rect {
+        egui::Rect::from_min_size(pos - self.size / 2., self.size)
     }
 
-    pub fn draw_rotate_center(&self, ui: &mut egui::Ui, angle: f32) {
+    pub fn draw_rotate_center(&self, ui: &mut egui::Ui, pos: egui::Pos2, angle: f32) {
         ui.put(
-            self.rect(),
+            self.rect(pos),
             egui::Image::new(self.texture_id, self.size).rotate(angle, egui::Vec2::splat(0.5)),
         );
     }
 
-    pub fn draw(&self, ui: &mut egui::Ui) {
-        ui.put(self.rect(), egui::Image::new(self.texture_id, self.size));
+    pub fn draw(&self, ui: &mut egui::Ui, pos: egui::Pos2) {
+        ui.put(self.rect(pos), egui::Image::new(self.texture_id, self.size));
     }
 }
@@ -0,0 +1,350 @@
+use crate::families::circle_radius;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+
+/// Steps of the force directed layout, the moves get smaller at every step
+const FORCE_DIRECTED_ITERATIONS: usize = 300;
+/// Pull of every node towards the center, so that the components stay together
+const GRAVITY: f32 = 0.1;
+/// Smallest distance between two nodes used by the forces
+const MIN_DISTANCE: f32 = 0.01;
+
+/// Id of every node with its position
+pub type NodePositions = Vec<(usize, (f32, f32))>;
+
+/// Way to place the nodes of a graph
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Layout {
+    /// Fruchterman–Reingold: the edges pull their nodes together and every node pushes the others
+    #[default]
+    ForceDirected,
+    /// Every node on a circle, in the order of their angle around the center of the graph
+    Circular,
+    /// Rows by distance to the root (an id), the children below their parent
+    Tree { root: usize },
+}
+
+impl Layout {
+    /// Every layout with a default parameter
+    pub const ALL: [Layout; 3] = [
+        Layout::ForceDirected,
+        Layout::Circular,
+        Layout::Tree { root: 0 },
+    ];
+
+    /// Checks if the two layouts are the same, whatever their parameter
+    pub fn same_kind(&self, other: &Layout) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layout::ForceDirected => write!(f, "Force directed")?,
+            Layout::Circular => write!(f, "Circular")?,
+            Layout::Tree { .. } => write!(f, "Tree")?,
+        }
+        Ok(())
+    }
+}
+
+fn centroid(positions: &[(f32, f32)]) -> (f32, f32) {
+    let n = positions.len().max(1) as f32;
+    let (x, y) = positions
+        .iter()
+        .fold((0., 0.), |(x, y), &(px, py)| (x + px, y + py));
+    (x / n, y / n)
+}
+
+/// Fruchterman–Reingold layout starting from the current positions,
+/// the joined nodes end up about `spacing` apart around the same center
+pub fn force_directed(
+    positions: &[(f32, f32)],
+    edges: &[(usize, usize)],
+    spacing: f32,
+) -> Vec<(f32, f32)> {
+    let n = positions.len();
+    let mut positions = positions.to_vec();
+    if n < 2 {
+        return positions;
+    }
+    let center = centroid(&positions);
+    // the largest move of a node, it cools down to 0
+    let mut temperature = spacing * (n as f32).sqrt();
+    let cooling = temperature / FORCE_DIRECTED_ITERATIONS as f32;
+
+    for _ in 0..FORCE_DIRECTED_ITERATIONS {
+        let mut moves = vec![(0., 0.); n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let (mut dx, mut dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                let mut distance = dx.hypot(dy);
+                if distance < MIN_DISTANCE {
+                    // nodes at the same place are pushed in a direction given by their indices
+                    let angle = (i * n + j) as f32;
+                    (dx, dy) = (MIN_DISTANCE * angle.cos(), MIN_DISTANCE * angle.sin());
+                    distance = MIN_DISTANCE;
+                }
+                let force = spacing * spacing / distance / distance;
+                moves[i].0 += dx * force;
+                moves[i].1 += dy * force;
+                moves[j].0 -= dx * force;
+                moves[j].1 -= dy * force;
+            }
+        }
+        for &(i, j) in edges {
+            let (dx, dy) = (
+                positions[i].0 - positions[j].0,
+                positions[i].1 - positions[j].1,
+            );
+            let force = dx.hypot(dy) / spacing;
+            moves[i].0 -= dx * force;
+            moves[i].1 -= dy * force;
+            moves[j].0 += dx * force;
+            moves[j].1 += dy * force;
+        }
+        for (pos, (mut dx, mut dy)) in positions.iter_mut().zip(moves) {
+            dx -= GRAVITY * (pos.0 - center.0);
+            dy -= GRAVITY * (pos.1 - center.1);
+            let length = dx.hypot(dy);
+            if length > 0. {
+                let step = length.min(temperature) / length;
+                pos.0 += dx * step;
+                pos.1 += dy * step;
+            }
+        }
+        temperature -= cooling;
+    }
+
+    // the graph does not move away from where it was
+    let (x, y) = centroid(&positions);
+    positions
+        .into_iter()
+        .map(|(px, py)| (px - x + center.0, py - y + center.1))
+        .collect()
+}
+
+/// Every node on a circle around the center of the graph, `spacing` apart,
+/// in the order of their angle so that the graph keeps its orientation
+pub fn circular(positions: &[(f32, f32)], spacing: f32) -> Vec<(f32, f32)> {
+    let n = positions.len();
+    let center = centroid(positions);
+    let angle = |i: usize| (positions[i].1 - center.1).atan2(positions[i].0 - center.0);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| angle(i).total_cmp(&angle(j)));
+
+    let radius = circle_radius(n, spacing);
+    let start = order.first().map_or(0., |&i| angle(i));
+    let mut circle = vec![center; n];
+    for (k, &i) in order.iter().enumerate() {
+        let angle = start + TAU * k as f32 / n as f32;
+        circle[i] = (
+            center.0 + radius * angle.cos(),
+            center.1 + radius * angle.sin(),
+        );
+    }
+    circle
+}
+
+/// Hierarchical layout of the breadth first search tree from the node at index `root`:
+/// one row per depth `spacing` apart, every parent above the middle of its children
+/// and the children in the order of their x position
+///
+/// The root keeps its position and the other components are put on its right,
+/// each one rooted at its first node.
+pub fn tree(
+    positions: &[(f32, f32)],
+    edges: &[(usize, usize)],
+    root: usize,
+    spacing: f32,
+) -> Vec<(f32, f32)> {
+    let n = positions.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut neighbours = vec![Vec::new(); n];
+    for &(i, j) in edges {
+        neighbours[i].push(j);
+        neighbours[j].push(i);
+    }
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut depths: Vec<Option<usize>> = vec![None; n];
+    let mut roots = Vec::new();
+    for start in std::iter::once(root.min(n - 1)).chain(0..n) {
+        if depths[start].is_some() {
+            continue;
+        }
+        roots.push(start);
+        depths[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let depth = depths[node].unwrap_or(0);
+            for &adj in neighbours[node].iter() {
+                if depths[adj].is_none() {
+                    depths[adj] = Some(depth + 1);
+                    children[node].push(adj);
+                    queue.push_back(adj);
+                }
+            }
+        }
+    }
+    for list in children.iter_mut() {
+        list.sort_by(|&i, &j| positions[i].0.total_cmp(&positions[j].0));
+    }
+
+    // the leaves get the next column in depth first order, so that every subtree
+    // takes consecutive columns, then the parents are centered above their children
+    let mut columns = vec![0.; n];
+    let mut next_column = 0.;
+    for &start in roots.iter() {
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            if children[node].is_empty() {
+                columns[node] = next_column;
+                next_column += 1.;
+            }
+            stack.extend(children[node].iter().rev());
+        }
+        for &node in order.iter().rev() {
+            if let (Some(&first), Some(&last)) = (children[node].first(), children[node].last()) {
+                columns[node] = (columns[first] + columns[last]) / 2.;
+            }
+        }
+        // a free column between the components
+        next_column += 1.;
+    }
+
+    let (root, root_pos) = (roots[0], positions[roots[0]]);
+    (0..n)
+        .map(|i| {
+            (
+                root_pos.0 + (columns[i] - columns[root]) * spacing,
+                root_pos.1 + depths[i].unwrap_or(0) as f32 * spacing,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::test_inputs::{distance, random_points};
+    use crate::generator::Rng;
+
+    const EPSILON: f32 = 1e-3;
+
+    #[test]
+    fn circular_keeps_the_center_and_the_order() {
+        let mut rng = Rng::new(3);
+        for n in 2..12 {
+            let positions = random_points(&mut rng, n, 400.);
+            let circle = circular(&positions, 50.);
+            let center = centroid(&positions);
+            let radius = circle_radius(n, 50.);
+            assert!(circle
+                .iter()
+                .all(|&pos| (distance(pos, center) - radius).abs() < EPSILON));
+            let (x, y) = centroid(&circle);
+            assert!(distance((x, y), center) < EPSILON);
+
+            // the neighbours on the circle are the neighbours by angle, `spacing` apart
+            let angle = |pos: (f32, f32)| (pos.1 - center.1).atan2(pos.0 - center.0);
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&i, &j| angle(positions[i]).total_cmp(&angle(positions[j])));
+            for k in 0..n {
+                let (i, j) = (order[k], order[(k + 1) % n]);
+                assert!((distance(circle[i], circle[j]) - 50.).abs() < EPSILON);
+            }
+        }
+        assert_eq!(circular(&[], 50.), Vec::new());
+        assert_eq!(circular(&[(3., 4.)], 50.), vec![(3., 4.)]);
+    }
+
+    #[test]
+    fn tree_puts_the_children_below_their_parent() {
+        //       0
+        //     /   \
+        //    1     2
+        //   / \     \
+        //  3   4     5
+        // the x positions give the order of the children
+        let positions = [(10., 10.), (0., 0.), (5., 0.), (2., 0.), (1., 0.), (0., 0.)];
+        let edges = [(0, 2), (1, 0), (1, 3), (4, 1), (2, 5)];
+        let laid_out = tree(&positions, &edges, 0, 10.);
+
+        assert_eq!(laid_out[0], (10., 10.));
+        let depths = [0., 1., 1., 2., 2., 2.];
+        for (pos, depth) in laid_out.iter().zip(depths) {
+            assert_eq!(pos.1, 10. + depth * 10.);
+        }
+        // the leaves are one column apart, in the order of their x position
+        assert_eq!(laid_out[4].0 + 10., laid_out[3].0);
+        assert_eq!(laid_out[3].0 + 10., laid_out[5].0);
+        // every parent above the middle of its children
+        assert_eq!(laid_out[1].0, (laid_out[4].0 + laid_out[3].0) / 2.);
+        assert_eq!(laid_out[2].0, laid_out[5].0);
+        assert_eq!(laid_out[0].0, (laid_out[1].0 + laid_out[2].0) / 2.);
+    }
+
+    #[test]
+    fn tree_puts_the_other_components_on_the_right() {
+        let positions = [(0., 0.), (0., 0.), (0., 0.), (0., 0.), (0., 0.)];
+        // the root 3 is alone, then the path 0 - 1 and the node 2, then 4
+        let laid_out = tree(&positions, &[(0, 1)], 3, 20.);
+        assert_eq!(laid_out[3], (0., 0.));
+        assert_eq!(laid_out[0], (40., 0.));
+        assert_eq!(laid_out[1], (40., 20.));
+        assert_eq!(laid_out[2], (80., 0.));
+        assert_eq!(laid_out[4], (120., 0.));
+        assert_eq!(tree(&[], &[], 0, 20.), Vec::new());
+    }
+
+    #[test]
+    fn force_directed_brings_the_joined_nodes_together() {
+        let mut rng = Rng::new(8);
+        // a path of nodes placed at random
+        let n = 10;
+        let positions = random_points(&mut rng, n, 400.);
+        let edges: Vec<(usize, usize)> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let laid_out = force_directed(&positions, &edges, 40.);
+        assert_eq!(laid_out, force_directed(&positions, &edges, 40.));
+
+        let center = centroid(&positions);
+        assert!(distance(centroid(&laid_out), center) < EPSILON);
+        // the joined nodes are about `spacing` apart, closer than the others
+        let joined: f32 = edges
+            .iter()
+            .map(|&(i, j)| distance(laid_out[i], laid_out[j]))
+            .sum::<f32>()
+            / edges.len() as f32;
+        assert!((20.0..80.).contains(&joined), "{joined}");
+        for i in 0..n {
+            for j in i + 2..n {
+                assert!(distance(laid_out[i], laid_out[j]) > MIN_DISTANCE);
+            }
+        }
+        let ends = distance(laid_out[0], laid_out[n - 1]);
+        assert!(ends > joined * 2., "{ends} {joined}");
+    }
+
+    #[test]
+    fn force_directed_separates_the_nodes_at_the_same_place() {
+        let positions = vec![(5., 5.); 4];
+        let laid_out = force_directed(&positions, &[(0, 1), (1, 2), (2, 3)], 30.);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                assert!(distance(laid_out[i], laid_out[j]) > 1., "{laid_out:?}");
+            }
+        }
+        assert_eq!(force_directed(&[(1., 2.)], &[], 30.), vec![(1., 2.)]);
+        assert_eq!(force_directed(&[], &[], 30.), Vec::new());
+    }
+}
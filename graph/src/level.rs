@@ -0,0 +1,367 @@
+#[cfg(feature = "gui")]
+use crate::graph::Graph;
+use crate::model::Model;
+use crate::weighting::Weighting;
+
+/// Town of the Muddy City game
+///
+/// A level is a text file with one instruction per line,
+/// the lines starting with `#` are comments:
+/// ```text
+/// name Muddy City
+/// difficulty 1
+/// roads cobblestone
+/// house 100 150 house1
+/// house 300 150
+/// road 0 1 4
+/// ```
+/// `difficulty` goes from 1 to 5 stars,
+/// `house x y [texture]` adds a house at a position with an optional texture,
+/// `road id1 id2 cost` adds a road between two houses (numbered from 0 in the file order)
+/// with a finite cost that is not negative, there is at most one road between two houses,
+/// `roads straight|cobblestone` chooses how the roads are drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub difficulty: u32,
+    pub cobblestone: bool,
+    pub houses: Vec<House>,
+    pub roads: Vec<(usize, usize, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct House {
+    pub pos: (f32, f32),
+    pub texture: Option<String>,
+}
+
+/// Error while reading a level, with the line where it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for LevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+fn parse_arg<T: std::str::FromStr>(
+    arg: Option<&str>,
+    what: &str,
+    line: usize,
+) -> Result<T, LevelError> {
+    let error = |message: String| LevelError { line, message };
+    let arg = arg.ok_or_else(|| error(format!("missing {what}")))?;
+    arg.parse()
+        .map_err(|_| error(format!("invalid {what} '{arg}'")))
+}
+
+/// `parse_arg` for a number that must be finite ("inf" and "NaN" are valid `f32`)
+fn parse_finite(arg: Option<&str>, what: &str, line: usize) -> Result<f32, LevelError> {
+    let value: f32 = parse_arg(arg, what, line)?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(LevelError {
+            line,
+            message: format!("{what} must be finite"),
+        })
+    }
+}
+
+impl Level {
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let mut level = Level {
+            name: String::from("Unnamed town"),
+            difficulty: 1,
+            cobblestone: false,
+            houses: Vec::new(),
+            roads: Vec::new(),
+        };
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let mut args = rest.split_whitespace();
+
+            match keyword {
+                "name" => level.name = rest.trim().to_string(),
+                "difficulty" => {
+                    level.difficulty = parse_arg(args.next(), "difficulty", line_number)?;
+                    if !(1..=5).contains(&level.difficulty) {
+                        return Err(LevelError {
+                            line: line_number,
+                            message: format!(
+                                "difficulty {} is not between 1 and 5",
+                                level.difficulty
+                            ),
+                        });
+                    }
+                }
+                "roads" => match args.next() {
+                    Some("straight") => level.cobblestone = false,
+                    Some("cobblestone") => level.cobblestone = true,
+                    _ => {
+                        return Err(LevelError {
+                            line: line_number,
+                            message: String::from("roads must be straight or cobblestone"),
+                        })
+                    }
+                },
+                "house" => {
+                    let x = parse_finite(args.next(), "x position", line_number)?;
+                    let y = parse_finite(args.next(), "y position", line_number)?;
+                    level.houses.push(House {
+                        pos: (x, y),
+                        texture: args.next().map(String::from),
+                    });
+                }
+                "road" => {
+                    let id1: usize = parse_arg(args.next(), "house", line_number)?;
+                    let id2: usize = parse_arg(args.next(), "house", line_number)?;
+                    let cost = parse_finite(args.next(), "cost", line_number)?;
+                    if cost < 0. {
+                        return Err(LevelError {
+                            line: line_number,
+                            message: format!("negative cost {cost}"),
+                        });
+                    }
+                    if id1 >= level.houses.len() || id2 >= level.houses.len() || id1 == id2 {
+                        return Err(LevelError {
+                            line: line_number,
+                            message: format!("invalid road between {id1} and {id2}"),
+                        });
+                    }
+                    if level
+                        .roads
+                        .iter()
+                        .any(|&(i, j, _)| (i, j) == (id1, id2) || (j, i) == (id1, id2))
+                    {
+                        return Err(LevelError {
+                            line: line_number,
+                            message: format!("several roads between {id1} and {id2}"),
+                        });
+                    }
+                    level.roads.push((id1, id2, cost));
+                }
+                _ => {
+                    return Err(LevelError {
+                        line: line_number,
+                        message: format!("unknown instruction '{keyword}'"),
+                    })
+                }
+            }
+        }
+        Ok(level)
+    }
+
+    /// Read every `.level` file of a directory, sorted by difficulty then by name
+    pub fn load_dir(
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Vec<Result<Level, (String, LevelError)>>> {
+        let mut levels = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(false, |extension| extension == "level")
+            {
+                let file_name = path.display().to_string();
+                let text = std::fs::read_to_string(&path)?;
+                levels.push(Level::parse(&text).map_err(|error| (file_name, error)));
+            }
+        }
+        levels.sort_by(|l1, l2| match (l1, l2) {
+            (Ok(l1), Ok(l2)) => (l1.difficulty, &l1.name).cmp(&(l2.difficulty, &l2.name)),
+            (l1, l2) => l1.is_err().cmp(&l2.is_err()),
+        });
+        Ok(levels)
+    }
+
+    /// Model of the town, the costs of the roads are its explicit weights
+    /// the id of every node is the number of its house
+    pub fn to_model(&self) -> Model {
+        let mut model = Model::default();
+        model.set_weighting(Weighting::Explicit);
+        for house in self.houses.iter() {
+            model.add_node(house.pos);
+        }
+        for &(id1, id2, cost) in self.roads.iter() {
+            model.add_edge(id1, id2);
+            model.set_weight(id1, id2, Some(cost));
+        }
+        model
+    }
+
+    /// Graph of the town, the costs of the roads are its explicit weights
+    /// the id of every node is the number of its house
+    ///
+    /// The houses are drawn as circles and the roads as straight lines:
+    /// the textures are loaded by the app, which gives them to the graph with
+    /// `Graph::set_texture_node` and `Graph::set_textures_vertex`.
+    #[cfg(feature = "gui")]
+    pub fn to_graph(&self, stroke: egui::Stroke) -> Graph {
+        let mut graph = Graph::default();
+        graph.set_weighting(Weighting::Explicit);
+        let ids: Vec<usize> = self
+            .houses
+            .iter()
+            .map(|house| graph.add_node(egui::Pos2::new(house.pos.0, house.pos.1), stroke))
+            .collect();
+        for &(house1, house2, cost) in self.roads.iter() {
+            let (id1, id2) = (ids[house1], ids[house2]);
+            graph.add_edge(id1, id2);
+            graph.set_weight(id1, id2, Some(cost));
+        }
+        // the town is not built by the player
+        graph.clear_history();
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOWN: &str = "# a small town
+name Small town
+difficulty 2
+roads cobblestone
+
+house 100 150 house1
+house 300 150
+house 200 -50.5 house2
+road 0 1 4
+road 1 2 0
+road 0 2 2.5
+";
+
+    fn error_line(text: &str) -> usize {
+        Level::parse(text).unwrap_err().line
+    }
+
+    #[test]
+    fn parse_a_valid_level() {
+        let level = Level::parse(TOWN).unwrap();
+        assert_eq!(level.name, "Small town");
+        assert_eq!(level.difficulty, 2);
+        assert!(level.cobblestone);
+        assert_eq!(
+            level.houses,
+            vec![
+                House {
+                    pos: (100., 150.),
+                    texture: Some(String::from("house1")),
+                },
+                House {
+                    pos: (300., 150.),
+                    texture: None,
+                },
+                House {
+                    pos: (200., -50.5),
+                    texture: Some(String::from("house2")),
+                },
+            ]
+        );
+        assert_eq!(level.roads, vec![(0, 1, 4.), (1, 2, 0.), (0, 2, 2.5)]);
+    }
+
+    #[test]
+    fn the_defaults_are_kept_without_instructions() {
+        let level = Level::parse("# nothing but comments\n\n").unwrap();
+        assert_eq!(level.name, "Unnamed town");
+        assert_eq!(level.difficulty, 1);
+        assert!(!level.cobblestone);
+        assert!(level.houses.is_empty() && level.roads.is_empty());
+    }
+
+    #[test]
+    fn the_model_has_the_costs_as_weights() {
+        let model = Level::parse(TOWN).unwrap().to_model();
+        assert_eq!(model.weighting(), Weighting::Explicit);
+        assert_eq!(model.nodes().len(), 3);
+        assert_eq!(model.weight(0, 1), Some(4.));
+        assert_eq!(model.weight(2, 0), Some(2.5));
+    }
+
+    #[test]
+    fn reject_invalid_costs() {
+        let houses = "house 0 0\nhouse 10 0\n";
+        for cost in ["NaN", "inf", "-inf", "-1", "-0.5", "four", ""] {
+            let text = format!("{houses}road 0 1 {cost}");
+            assert_eq!(error_line(&text), 3, "cost '{cost}'");
+        }
+    }
+
+    #[test]
+    fn reject_malformed_lines() {
+        assert_eq!(error_line("house 0"), 1);
+        assert_eq!(error_line("house 0 NaN"), 1);
+        assert_eq!(error_line("house x 0"), 1);
+        assert_eq!(error_line("difficulty hard"), 1);
+        assert_eq!(error_line("roads muddy"), 1);
+        assert_eq!(error_line("name Town\nbridge 0 1"), 2);
+        // roads between unknown houses or from a house to itself
+        assert_eq!(error_line("house 0 0\nroad 0 1 1"), 2);
+        assert_eq!(error_line("house 0 0\nroad 0 0 1"), 2);
+        assert_eq!(error_line("house 0 0\nhouse 1 1\nroad 0 -1 1"), 3);
+    }
+
+    #[test]
+    fn reject_repeated_roads() {
+        let houses = "house 0 0\nhouse 10 0\nhouse 0 10\n";
+        for road in ["0 1", "1 0"] {
+            let text = format!("{houses}road 0 1 1\nroad 1 2 1\nroad {road} 2");
+            let error = Level::parse(&text).unwrap_err();
+            assert_eq!(error.line, 6, "road {road}");
+            assert_eq!(
+                error.message,
+                format!("several roads between {}", road.replace(' ', " and "))
+            );
+        }
+    }
+
+    #[test]
+    fn the_difficulty_goes_from_1_to_5() {
+        for difficulty in 1..=5 {
+            let level = Level::parse(&format!("difficulty {difficulty}")).unwrap();
+            assert_eq!(level.difficulty, difficulty);
+        }
+        for difficulty in ["0", "6", "1000000", "-1"] {
+            let text = format!("name Town\ndifficulty {difficulty}");
+            assert_eq!(error_line(&text), 2, "difficulty {difficulty}");
+        }
+        let error = Level::parse("difficulty 6").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: difficulty 6 is not between 1 and 5"
+        );
+    }
+
+    #[test]
+    fn the_levels_of_the_app_are_valid() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../data/levels");
+        let levels = Level::load_dir(dir).unwrap();
+        assert!(!levels.is_empty());
+        for level in levels {
+            if let Err((file, error)) = level {
+                panic!("{file}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_error_shows_the_line() {
+        let error = Level::parse("house 0 0\nroad 0 1 1").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: invalid road between 0 and 1");
+    }
+}
@@ -1,7 +1,33 @@
 #![warn(clippy::all, rust_2018_idioms)]
+pub mod bridges;
+#[cfg(feature = "gui")]
+pub mod camera;
+#[cfg(feature = "gui")]
 mod circle;
+pub mod components;
+pub mod delaunay;
+pub mod families;
+pub mod generator;
+#[cfg(feature = "gui")]
 pub mod graph;
+#[cfg(feature = "gui")]
+pub mod highlight;
+#[cfg(feature = "gui")]
+pub mod history;
+#[cfg(feature = "gui")]
 mod image;
+pub mod layout;
+pub mod level;
+pub mod model;
+#[cfg(feature = "gui")]
 mod node;
+pub mod proximity;
+pub mod save;
+pub mod shortest_path;
+pub mod spanning_tree;
+#[cfg(feature = "gui")]
 mod state;
+pub mod union_find;
+#[cfg(feature = "gui")]
 mod vertex;
+pub mod weighting;
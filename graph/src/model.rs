@@ -0,0 +1,837 @@
+use crate::bridges::{critical_parts, CriticalParts};
+use crate::components::Components;
+use crate::delaunay::euclidean_mst;
+use crate::layout::{circular, force_directed, tree, Layout, NodePositions};
+use crate::proximity::ProximityGraph;
+use crate::save::GraphData;
+use crate::shortest_path::{Path, ShortestPathAlgorithm};
+use crate::spanning_tree::{SpanningForest, SpanningTreeAlgorithm, Step, WeightedEdge};
+use crate::weighting::Weighting;
+use std::collections::{HashMap, VecDeque};
+
+/// Node of a model, placed in the coordinates of the graph, with its own data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelNode<N = ()> {
+    pub id: usize,
+    pub pos: (f32, f32),
+    pub data: N,
+}
+
+/// Edge between two nodes given by their ids, with its own data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelEdge<E = ()> {
+    pub id1: usize,
+    pub id2: usize,
+    /// Explicit weight, used with `Weighting::Explicit`
+    pub weight: Option<f32>,
+    pub data: E,
+}
+
+/// Graph without anything to draw or edit it: positions, adjacencies and weights
+///
+/// Every node holds a `N` and every edge a `E` (names, population, kind of road...).
+/// The algorithms work on it and it does not need egui (the `gui` feature),
+/// the drawable `Graph` is converted with `Graph::to_model` or `Graph::to_model_with`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model<N = (), E = ()> {
+    nodes: Vec<ModelNode<N>>,
+    /// `adjacencies[i]` are the ids of the neighbours of `nodes[i]`
+    adjacencies: Vec<Vec<usize>>,
+    edges: Vec<ModelEdge<E>>,
+    /// Id given to the next node
+    max_id: usize,
+    /// Index in `nodes` of every node id
+    indices: HashMap<usize, usize>,
+    weighting: Weighting,
+}
+
+impl<N, E> Default for Model<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            adjacencies: Vec::new(),
+            edges: Vec::new(),
+            max_id: 0,
+            indices: HashMap::new(),
+            weighting: Weighting::default(),
+        }
+    }
+}
+
+impl<N: Default, E: Default> Model<N, E> {
+    /// Model with a node at every position, `adjacencies[i]` are the neighbours of the i-th one
+    ///
+    /// Fails if there is not one adjacency list per position or if a neighbour is not a node.
+    pub fn new(positions: Vec<(f32, f32)>, adjacencies: Vec<Vec<usize>>) -> Result<Self, String> {
+        let n_nodes = positions.len();
+        if adjacencies.len() != n_nodes {
+            return Err(format!(
+                "{} adjacency lists for {n_nodes} nodes",
+                adjacencies.len()
+            ));
+        }
+        let mut model = Model::default();
+        for pos in positions {
+            model.add_node(pos);
+        }
+        for (id1, adjacency) in adjacencies.iter().enumerate() {
+            for &id2 in adjacency.iter() {
+                if id2 >= n_nodes {
+                    return Err(format!("invalid adjacency between {id1} and {id2}"));
+                }
+                model.add_edge(id1, id2);
+            }
+        }
+        Ok(model)
+    }
+
+    /// Model of a saved graph, which must be valid (see `GraphData::from_json`)
+    pub fn from_data(data: &GraphData) -> Self {
+        Model::from_parts(
+            data.nodes
+                .iter()
+                .map(|node| ModelNode {
+                    id: node.id,
+                    pos: (node.pos[0], node.pos[1]),
+                    data: N::default(),
+                })
+                .collect(),
+            data.adjacencies.clone(),
+            data.vertices
+                .iter()
+                .map(|vertex| ModelEdge {
+                    id1: vertex.node_id1,
+                    id2: vertex.node_id2,
+                    weight: vertex.weight,
+                    data: E::default(),
+                })
+                .collect(),
+            data.weighting,
+        )
+    }
+
+    /// Load a model from a graph saved as JSON, the way it is drawn is ignored
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Model::from_data(&GraphData::from_json(json)?))
+    }
+
+    /// Add a node with the default data and return its id
+    pub fn add_node(&mut self, pos: (f32, f32)) -> usize {
+        self.add_node_with(pos, N::default())
+    }
+
+    /// Add an edge with the default data, returns false if it already exists
+    pub fn add_edge(&mut self, id1: usize, id2: usize) -> bool {
+        self.add_edge_with(id1, id2, E::default())
+    }
+
+    /// Add the edges of the Euclidean minimum spanning tree of the nodes
+    pub fn connect_euclidean_mst(&mut self) {
+        for (id1, id2) in self.euclidean_mst() {
+            self.add_edge(id1, id2);
+        }
+    }
+
+    /// Add the edges of a proximity graph of the nodes
+    pub fn connect_proximity(&mut self, proximity_graph: ProximityGraph) {
+        for (id1, id2) in self.proximity_edges(proximity_graph) {
+            self.add_edge(id1, id2);
+        }
+    }
+}
+
+impl<N, E> Model<N, E> {
+    /// Model made of nodes and edges that are already consistent
+    pub(crate) fn from_parts(
+        nodes: Vec<ModelNode<N>>,
+        adjacencies: Vec<Vec<usize>>,
+        edges: Vec<ModelEdge<E>>,
+        weighting: Weighting,
+    ) -> Self {
+        let mut model = Model {
+            max_id: nodes.iter().map(|node| node.id + 1).max().unwrap_or(0),
+            nodes,
+            adjacencies,
+            edges,
+            weighting,
+            ..Default::default()
+        };
+        model.reindex();
+        model
+    }
+
+    /// The same model with other data on its nodes and edges
+    pub fn map<N2, E2>(
+        self,
+        mut node_data: impl FnMut(N) -> N2,
+        mut edge_data: impl FnMut(E) -> E2,
+    ) -> Model<N2, E2> {
+        Model {
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|node| ModelNode {
+                    id: node.id,
+                    pos: node.pos,
+                    data: node_data(node.data),
+                })
+                .collect(),
+            adjacencies: self.adjacencies,
+            edges: self
+                .edges
+                .into_iter()
+                .map(|edge| ModelEdge {
+                    id1: edge.id1,
+                    id2: edge.id2,
+                    weight: edge.weight,
+                    data: edge_data(edge.data),
+                })
+                .collect(),
+            max_id: self.max_id,
+            indices: self.indices,
+            weighting: self.weighting,
+        }
+    }
+
+    fn reindex(&mut self) {
+        self.indices = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+    }
+
+    // SETTERS /////////////////////////////////////////////////////////////////////
+    pub fn set_weighting(&mut self, weighting: Weighting) -> &mut Self {
+        self.weighting = weighting;
+        self
+    }
+
+    pub fn set_pos(&mut self, id: usize, pos: (f32, f32)) -> &mut Self {
+        if let Some(index) = self.node_index(id) {
+            self.nodes[index].pos = pos;
+        }
+        self
+    }
+
+    /// Explicit weight of the edge between two nodes, nothing is done if there is no edge
+    pub fn set_weight(&mut self, id1: usize, id2: usize, weight: Option<f32>) -> &mut Self {
+        if let Some(index) = self.edge_index(id1, id2) {
+            self.edges[index].weight = weight;
+        }
+        self
+    }
+
+    // GETTERS /////////////////////////////////////////////////////////////////////
+    pub fn nodes(&self) -> &[ModelNode<N>] {
+        &self.nodes
+    }
+
+    /// The nodes, whose ids must not be changed
+    #[cfg(feature = "gui")]
+    pub(crate) fn nodes_mut(&mut self) -> &mut [ModelNode<N>] {
+        &mut self.nodes
+    }
+
+    pub fn edges(&self) -> &[ModelEdge<E>] {
+        &self.edges
+    }
+
+    /// The edges, whose nodes must not be changed
+    #[cfg(feature = "gui")]
+    pub(crate) fn edges_mut(&mut self) -> &mut [ModelEdge<E>] {
+        &mut self.edges
+    }
+
+    /// `adjacencies()[i]` are the ids of the neighbours of `nodes()[i]`
+    pub fn adjacencies(&self) -> &[Vec<usize>] {
+        &self.adjacencies
+    }
+
+    /// Id given to the next node
+    pub fn next_id(&self) -> usize {
+        self.max_id
+    }
+
+    pub fn weighting(&self) -> Weighting {
+        self.weighting
+    }
+
+    /// Index in `nodes` of the node with this id
+    pub fn node_index(&self, id: usize) -> Option<usize> {
+        self.indices.get(&id).copied()
+    }
+
+    pub fn node(&self, id: usize) -> Option<&ModelNode<N>> {
+        self.node_index(id).map(|index| &self.nodes[index])
+    }
+
+    pub fn pos(&self, id: usize) -> Option<(f32, f32)> {
+        self.node(id).map(|node| node.pos)
+    }
+
+    pub fn node_data(&self, id: usize) -> Option<&N> {
+        self.node(id).map(|node| &node.data)
+    }
+
+    pub fn node_data_mut(&mut self, id: usize) -> Option<&mut N> {
+        let index = self.node_index(id)?;
+        Some(&mut self.nodes[index].data)
+    }
+
+    /// Ids of the neighbours of a node
+    pub fn neighbours(&self, id: usize) -> &[usize] {
+        self.node_index(id)
+            .map_or(&[], |index| self.adjacencies[index].as_slice())
+    }
+
+    /// Distance between two nodes of the model, `None` if one of them is not in the model
+    pub fn distance(&self, id1: usize, id2: usize) -> Option<f32> {
+        let (x1, y1) = self.pos(id1)?;
+        let (x2, y2) = self.pos(id2)?;
+        Some((x2 - x1).hypot(y2 - y1))
+    }
+
+    /// Distance between the nodes of an edge, which are always in the model
+    fn length(&self, edge: &ModelEdge<E>) -> f32 {
+        self.distance(edge.id1, edge.id2).unwrap_or_default()
+    }
+
+    /// Index in `edges` of the edge between two nodes, in any direction
+    pub fn edge_index(&self, id1: usize, id2: usize) -> Option<usize> {
+        self.edges.iter().position(|edge| {
+            (edge.id1, edge.id2) == (id1, id2) || (edge.id1, edge.id2) == (id2, id1)
+        })
+    }
+
+    pub fn edge(&self, id1: usize, id2: usize) -> Option<&ModelEdge<E>> {
+        self.edge_index(id1, id2).map(|index| &self.edges[index])
+    }
+
+    pub fn edge_data(&self, id1: usize, id2: usize) -> Option<&E> {
+        self.edge(id1, id2).map(|edge| &edge.data)
+    }
+
+    pub fn edge_data_mut(&mut self, id1: usize, id2: usize) -> Option<&mut E> {
+        let index = self.edge_index(id1, id2)?;
+        Some(&mut self.edges[index].data)
+    }
+
+    /// Weight of the edge between two nodes with the weighting of the model
+    pub fn weight(&self, id1: usize, id2: usize) -> Option<f32> {
+        self.edge(id1, id2).map(|edge| self.edge_weight(edge))
+    }
+
+    /// Weight of an edge with the weighting of the model
+    pub fn edge_weight(&self, edge: &ModelEdge<E>) -> f32 {
+        self.weighting.weight(self.length(edge), edge.weight)
+    }
+
+    // EDIT ////////////////////////////////////////////////////////////////////////
+    /// Add a node and return its id
+    pub fn add_node_with(&mut self, pos: (f32, f32), data: N) -> usize {
+        let id = self.max_id;
+        self.max_id += 1;
+        self.indices.insert(id, self.nodes.len());
+        self.nodes.push(ModelNode { id, pos, data });
+        self.adjacencies.push(Vec::new());
+        id
+    }
+
+    /// Put back a node with no edge at `index` in `nodes`, with the id it had
+    #[cfg(feature = "gui")]
+    pub(crate) fn insert_node(&mut self, index: usize, node: ModelNode<N>) {
+        self.max_id = self.max_id.max(node.id + 1);
+        self.nodes.insert(index, node);
+        self.adjacencies.insert(index, Vec::new());
+        self.reindex();
+    }
+
+    /// Remove a node and its edges
+    pub fn rm_node(&mut self, id: usize) {
+        let Some(index) = self.node_index(id) else {
+            return;
+        };
+        self.edges.retain(|edge| edge.id1 != id && edge.id2 != id);
+        for adjacency in self.adjacencies.iter_mut() {
+            adjacency.retain(|&adj| adj != id);
+        }
+        self.nodes.remove(index);
+        self.adjacencies.remove(index);
+        self.reindex();
+    }
+
+    /// Add an edge between two different nodes, returns false if it already exists
+    pub fn add_edge_with(&mut self, id1: usize, id2: usize, data: E) -> bool {
+        let edge = ModelEdge {
+            id1,
+            id2,
+            weight: None,
+            data,
+        };
+        self.insert_edge(self.edges.len(), edge)
+    }
+
+    /// Add an edge at `index` in `edges`, returns false if it already exists
+    /// or if its nodes are not two different nodes of the model
+    pub(crate) fn insert_edge(&mut self, index: usize, edge: ModelEdge<E>) -> bool {
+        let (id1, id2) = (edge.id1, edge.id2);
+        let (Some(index1), Some(index2)) = (self.node_index(id1), self.node_index(id2)) else {
+            return false;
+        };
+        if id1 == id2 || self.adjacencies[index1].contains(&id2) {
+            return false;
+        }
+        self.adjacencies[index1].push(id2);
+        self.adjacencies[index2].push(id1);
+        self.edges.insert(index, edge);
+        true
+    }
+
+    /// Remove the edge between two nodes, returns false if there is no such edge
+    pub fn rm_edge(&mut self, id1: usize, id2: usize) -> bool {
+        let Some(index) = self.edge_index(id1, id2) else {
+            return false;
+        };
+        self.edges.remove(index);
+        for (id, adj) in [(id1, id2), (id2, id1)] {
+            if let Some(index) = self.node_index(id) {
+                self.adjacencies[index].retain(|&other| other != adj);
+            }
+        }
+        true
+    }
+
+    // ALGORITHMS //////////////////////////////////////////////////////////////////
+    /// Checks if the model has a cycle
+    pub fn has_cycle(&self) -> bool {
+        self.nodes.iter().any(|node| self.has_cycle_from(node.id))
+    }
+
+    /// Checks if there is a cycle in the component of `start`, false if it is not a node
+    pub fn has_cycle_from(&self, start: usize) -> bool {
+        if self.node_index(start).is_none() {
+            return false;
+        }
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = vec![(start, start)];
+        while let Some((last, current)) = queue.pop() {
+            let index = self.indices[&current];
+            if visited[index] {
+                // Already visited the node => there is a cycle
+                return true;
+            }
+            visited[index] = true;
+            // Add its neighbours to the queue with the exception of the node we came from
+            for &adj in self.adjacencies[index].iter() {
+                if adj != last {
+                    queue.push((current, adj));
+                }
+            }
+        }
+        false
+    }
+
+    /// Connected components, labeled by the index of the nodes in `nodes`
+    pub fn components(&self) -> Components {
+        Components::new(self.nodes.len(), &self.indexed_edges_by(|_| 0.))
+    }
+
+    /// Component of the node with the id `id`
+    pub fn component_of(&self, id: usize) -> Option<usize> {
+        self.node_index(id)
+            .map(|index| self.components().label(index))
+    }
+
+    /// Bridges (indices in `edges`) and articulation points (sorted ids) of the model
+    pub fn critical_parts(&self) -> CriticalParts {
+        let mut parts = critical_parts(self.nodes.len(), &self.indexed_edges_by(|_| 0.));
+        for point in parts.articulation_points.iter_mut() {
+            *point = self.nodes[*point].id;
+        }
+        // the nodes are not sorted by id once some of them were removed
+        parts.articulation_points.sort_unstable();
+        parts
+    }
+
+    /// Edges of the minimum spanning tree of the complete graph with the distances
+    /// as weights, as pairs of ids, without building the complete graph
+    pub fn euclidean_mst(&self) -> Vec<(usize, usize)> {
+        self.ids_of(euclidean_mst(&self.positions()))
+    }
+
+    /// Edges of a proximity graph of the nodes, as pairs of ids
+    pub fn proximity_edges(&self, proximity_graph: ProximityGraph) -> Vec<(usize, usize)> {
+        self.ids_of(proximity_graph.edges(&self.positions()))
+    }
+
+    /// Ids of the nodes with the positions where a layout places them,
+    /// an error if the root of a tree layout is not a node of the model
+    pub fn layout(&self, layout: Layout, spacing: f32) -> Result<NodePositions, String> {
+        let positions = self.positions();
+        let edges: Vec<(usize, usize)> = self
+            .indexed_edges_by(|_| 0.)
+            .into_iter()
+            .map(|(i, j, _)| (i, j))
+            .collect();
+        let positions = match layout {
+            Layout::ForceDirected => force_directed(&positions, &edges, spacing),
+            Layout::Circular => circular(&positions, spacing),
+            Layout::Tree { root } => {
+                let Some(root) = self.node_index(root) else {
+                    return Err(format!("no node {root} to root the tree"));
+                };
+                tree(&positions, &edges, root, spacing)
+            }
+        };
+        Ok(self
+            .nodes
+            .iter()
+            .zip(positions)
+            .map(|(node, pos)| (node.id, pos))
+            .collect())
+    }
+
+    /// Move the nodes where a layout places them, nothing is moved if it is an error
+    pub fn apply_layout(&mut self, layout: Layout, spacing: f32) -> Result<&mut Self, String> {
+        for (id, pos) in self.layout(layout, spacing)? {
+            self.set_pos(id, pos);
+        }
+        Ok(self)
+    }
+
+    fn positions(&self) -> Vec<(f32, f32)> {
+        self.nodes.iter().map(|node| node.pos).collect()
+    }
+
+    /// Pairs of node indices as pairs of ids
+    fn ids_of(&self, pairs: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        pairs
+            .into_iter()
+            .map(|(i, j)| (self.nodes[i].id, self.nodes[j].id))
+            .collect()
+    }
+
+    /// Ids of the nodes reachable from `start`, nearest first (breadth first search)
+    pub fn breadth_first(&self, start: usize) -> Vec<usize> {
+        let mut order = Vec::new();
+        let Some(index) = self.node_index(start) else {
+            return order;
+        };
+        let mut visited = vec![false; self.nodes.len()];
+        visited[index] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            for &adj in self.neighbours(current) {
+                let index = self.indices[&adj];
+                if !visited[index] {
+                    visited[index] = true;
+                    queue.push_back(adj);
+                }
+            }
+        }
+        order
+    }
+
+    /// Ids of the nodes reachable from `start`, in the order of a depth first search
+    pub fn depth_first(&self, start: usize) -> Vec<usize> {
+        let mut order = Vec::new();
+        if self.node_index(start).is_none() {
+            return order;
+        }
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            let index = self.indices[&current];
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            order.push(current);
+            // the first neighbour is visited first
+            stack.extend(self.adjacencies[index].iter().rev());
+        }
+        order
+    }
+
+    /// Every edge as the ids of its nodes and its weight, in the same order as `edges`
+    pub fn weighted_edges(&self) -> Vec<WeightedEdge> {
+        self.weighted_edges_by(|edge| self.edge_weight(edge))
+    }
+
+    /// `weighted_edges` with the weight of every edge given by a function
+    pub fn weighted_edges_by(&self, weight: impl Fn(&ModelEdge<E>) -> f32) -> Vec<WeightedEdge> {
+        self.edges
+            .iter()
+            .map(|edge| (edge.id1, edge.id2, weight(edge)))
+            .collect()
+    }
+
+    /// `weighted_edges` with the nodes given by their index in `nodes`,
+    /// as expected by the spanning tree algorithms
+    pub fn indexed_edges(&self) -> Vec<WeightedEdge> {
+        self.indexed_edges_by(|edge| self.edge_weight(edge))
+    }
+
+    pub fn indexed_edges_by(&self, weight: impl Fn(&ModelEdge<E>) -> f32) -> Vec<WeightedEdge> {
+        self.edges
+            .iter()
+            .map(|edge| {
+                (
+                    self.indices[&edge.id1],
+                    self.indices[&edge.id2],
+                    weight(edge),
+                )
+            })
+            .collect()
+    }
+
+    /// Sum of the weights of every edge
+    pub fn total_weight(&self) -> f32 {
+        self.total_weight_by(|edge| self.edge_weight(edge))
+    }
+
+    pub fn total_weight_by(&self, weight: impl Fn(&ModelEdge<E>) -> f32) -> f32 {
+        self.edges.iter().map(weight).sum()
+    }
+
+    /// Indices in `edges` of the edges of the minimum (or maximum) covering tree
+    pub fn covering_tree_edges(&self, algorithm: SpanningTreeAlgorithm, max: bool) -> Vec<usize> {
+        self.covering_tree_edges_by(algorithm, |edge| self.signed_weight(edge, max))
+    }
+
+    /// Indices in `edges` of the edges of the minimum covering tree for a weight function,
+    /// the maximum one is given by the opposite weights
+    pub fn covering_tree_edges_by(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        weight: impl Fn(&ModelEdge<E>) -> f32,
+    ) -> Vec<usize> {
+        algorithm.run(self.nodes.len(), &self.indexed_edges_by(weight))
+    }
+
+    /// Minimum (or maximum) spanning forest with the weight of the tree of every component,
+    /// its edges are indices in `edges`
+    pub fn spanning_forest(&self, algorithm: SpanningTreeAlgorithm, max: bool) -> SpanningForest {
+        SpanningForest::new(
+            self.nodes.len(),
+            &self.indexed_edges(),
+            self.covering_tree_edges(algorithm, max),
+        )
+    }
+
+    /// Every step of the computation of the covering tree,
+    /// the edge of a step is an index in `edges`
+    pub fn covering_tree_steps(&self, algorithm: SpanningTreeAlgorithm, max: bool) -> Vec<Step> {
+        self.covering_tree_steps_by(algorithm, |edge| self.signed_weight(edge, max))
+    }
+
+    pub fn covering_tree_steps_by(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        weight: impl Fn(&ModelEdge<E>) -> f32,
+    ) -> Vec<Step> {
+        algorithm.trace(self.nodes.len(), &self.indexed_edges_by(weight))
+    }
+
+    /// Shortest path between two nodes, its nodes are ids and its edges indices in `edges`
+    pub fn shortest_path(
+        &self,
+        algorithm: ShortestPathAlgorithm,
+        source: usize,
+        destination: usize,
+    ) -> Option<Path> {
+        self.shortest_path_by(algorithm, source, destination, |edge| {
+            self.edge_weight(edge)
+        })
+    }
+
+    /// `shortest_path` with the weight of every edge given by a function,
+    /// the weights must not be negative
+    pub fn shortest_path_by(
+        &self,
+        algorithm: ShortestPathAlgorithm,
+        source: usize,
+        destination: usize,
+        weight: impl Fn(&ModelEdge<E>) -> f32,
+    ) -> Option<Path> {
+        let edges = self.indexed_edges_by(weight);
+        // the straight line is never longer than a path when every edge weighs
+        // at least `scale` times its length, so A* stays exact with explicit weights
+        let scale = edges
+            .iter()
+            .zip(self.edges.iter())
+            .map(|(&(_, _, weight), edge)| weight / self.length(edge))
+            .filter(|ratio| !ratio.is_nan())
+            .fold(f32::INFINITY, f32::min)
+            .max(0.);
+        let scale = if scale.is_finite() { scale } else { 0. };
+        let (x, y) = self.pos(destination)?;
+        let heuristic = |index: usize| {
+            let pos = self.nodes[index].pos;
+            scale * (pos.0 - x).hypot(pos.1 - y)
+        };
+
+        let mut path = algorithm.run(
+            self.nodes.len(),
+            &edges,
+            self.node_index(source)?,
+            self.node_index(destination)?,
+            heuristic,
+        )?;
+        path.nodes = path
+            .nodes
+            .iter()
+            .map(|&index| self.nodes[index].id)
+            .collect();
+        Some(path)
+    }
+
+    /// The maximum covering tree is the minimum one with opposite weights
+    fn signed_weight(&self, edge: &ModelEdge<E>, max: bool) -> f32 {
+        let weight = self.edge_weight(edge);
+        if max {
+            -weight
+        } else {
+            weight
+        }
+    }
+}
+
+impl<N: Clone, E: Clone> Model<N, E> {
+    /// Model with the same nodes and only the edges of the covering tree
+    pub fn covering_tree(&self, algorithm: SpanningTreeAlgorithm, max: bool) -> Self {
+        self.subgraph_of_edges(&self.covering_tree_edges(algorithm, max))
+    }
+
+    /// Model with the same nodes and only the edges of the minimum covering tree
+    /// for a weight function
+    pub fn covering_tree_by(
+        &self,
+        algorithm: SpanningTreeAlgorithm,
+        weight: impl Fn(&ModelEdge<E>) -> f32,
+    ) -> Self {
+        self.subgraph_of_edges(&self.covering_tree_edges_by(algorithm, weight))
+    }
+
+    /// Model with the same nodes and only some edges, given by their index in `edges`
+    pub(crate) fn subgraph_of_edges(&self, edges: &[usize]) -> Self {
+        let edges: Vec<ModelEdge<E>> = edges.iter().map(|&e| self.edges[e].clone()).collect();
+        let mut adjacencies = vec![Vec::new(); self.nodes.len()];
+        for edge in edges.iter() {
+            adjacencies[self.indices[&edge.id1]].push(edge.id2);
+            adjacencies[self.indices[&edge.id2]].push(edge.id1);
+        }
+        Model {
+            nodes: self.nodes.clone(),
+            adjacencies,
+            edges,
+            max_id: self.max_id,
+            indices: self.indices.clone(),
+            weighting: self.weighting,
+        }
+    }
+}
+
+impl<N, E> std::fmt::Display for Model<N, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Adjacencies:")?;
+        for (node, adj) in self.nodes.iter().zip(self.adjacencies.iter()) {
+            write!(f, "\t {} {:?} -> ", node.id, node.pos)?;
+            for j in adj.iter() {
+                write!(f, "| {j} |")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Weighting: {}", self.weighting)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Square 0-1-2-3 with the diagonal 0-2
+    fn square() -> Model {
+        Model::new(
+            vec![(0., 0.), (3., 0.), (3., 4.), (0., 4.)],
+            vec![vec![1, 3, 2], vec![0, 2], vec![1, 3, 0], vec![2, 0]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new_checks_the_adjacencies() {
+        assert_eq!(square().edges().len(), 5);
+        assert!(Model::<(), ()>::new(vec![(0., 0.)], Vec::new()).is_err());
+        assert!(Model::<(), ()>::new(vec![(0., 0.), (1., 1.)], vec![vec![1], vec![2]]).is_err());
+    }
+
+    #[test]
+    fn unknown_ids_are_ignored() {
+        let mut model = square();
+        assert_eq!(model.distance(0, 2), Some(5.));
+        assert_eq!(model.distance(0, 7), None);
+        assert!(!model.has_cycle_from(7));
+        assert!(!model.rm_edge(0, 7));
+        assert!(!model.rm_edge(1, 3));
+        assert!(model.rm_edge(2, 0));
+        assert_eq!(model.edges().len(), 4);
+        assert_eq!(model.neighbours(0), &[1, 3]);
+        assert_eq!(model.neighbours(2), &[1, 3]);
+    }
+
+    #[test]
+    fn the_root_of_a_tree_layout_is_a_node() {
+        let mut model = square();
+        model.rm_node(0);
+        assert!(model.layout(Layout::Tree { root: 0 }, 10.).is_err());
+        assert!(model.apply_layout(Layout::Tree { root: 0 }, 10.).is_err());
+        let layout = model.layout(Layout::Tree { root: 3 }, 10.).unwrap();
+        let ids: Vec<usize> = layout.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        // the root keeps its position
+        assert_eq!(layout[2].1, model.pos(3).unwrap());
+    }
+
+    #[test]
+    fn detect_cycles() {
+        let mut model = square();
+        assert!(model.has_cycle());
+        model.rm_edge(0, 2);
+        model.rm_edge(2, 3);
+        assert!(!model.has_cycle());
+        assert!(!model.has_cycle_from(0));
+    }
+
+    #[test]
+    fn articulation_points_are_sorted_ids() {
+        // path 9 - 4 - 7 - 2 whose nodes are not in the order of their ids
+        let nodes = [9, 4, 7, 2]
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| ModelNode {
+                id,
+                pos: (i as f32, 0.),
+                data: (),
+            })
+            .collect();
+        let edges = [(9, 4), (4, 7), (7, 2)]
+            .into_iter()
+            .map(|(id1, id2)| ModelEdge {
+                id1,
+                id2,
+                weight: None,
+                data: (),
+            })
+            .collect();
+        let adjacencies = vec![vec![4], vec![9, 7], vec![4, 2], vec![7]];
+        let model: Model = Model::from_parts(nodes, adjacencies, edges, Weighting::default());
+        let parts = model.critical_parts();
+        assert_eq!(parts.articulation_points, vec![4, 7]);
+        assert_eq!(parts.bridges.len(), 3);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::circle::Circle;
+use crate::highlight::Highlight;
 use crate::image::Image;
+use crate::save::{NodeData, NodeStyle, TextureNames};
 
 #[derive(Debug, Clone, Copy)]
 enum Drawables {
@@ -7,34 +9,29 @@ enum Drawables {
     Image(Image),
 }
 
+/// Way a node is drawn, its position is the one of its node in the model of the graph
 #[derive(Debug, Clone, Copy)]
 pub struct Node {
     id: usize,
     is_dragging: bool,
     drag_start: egui::Pos2,
     drawable: Drawables,
+    highlight: Highlight,
 }
 
 impl Node {
     // CONSTUCTORS //////////////////////////////////////////////////////////////////
-    pub fn new_circle_node(id: Option<usize>, pos: egui::Pos2, stroke: egui::Stroke) -> Self {
+    pub fn new_circle_node(id: Option<usize>, stroke: egui::Stroke) -> Self {
         Self {
             id: id.unwrap_or(0),
             is_dragging: false,
             drag_start: egui::Pos2::ZERO,
-            drawable: Drawables::Circle(Circle::new(pos, stroke)),
+            drawable: Drawables::Circle(Circle::new(stroke)),
+            highlight: Highlight::None,
         }
     }
 
     // GETTERS //////////////////////////////////////////////////////////////////////
-    pub fn pos(&self) -> egui::Pos2 {
-        match self.drawable {
-            Drawables::Circle(circle) => circle.center(),
-            // Choose if you want the center or the top left corner of the image
-            Drawables::Image(image) => image.pos(),
-        }
-    }
-
     pub fn id(&self) -> usize {
         self.id
     }
@@ -47,15 +44,11 @@ impl Node {
         self.drag_start
     }
 
-    // SETTER //////////////////////////////////////////////////////////////////////
-    pub fn set_pos(&mut self, pos: egui::Pos2) -> &mut Self {
-        match self.drawable {
-            Drawables::Circle(ref mut circle) => circle.set_center(pos),
-            Drawables::Image(ref mut image) => image.set_pos(pos),
-        }
-        self
+    pub fn highlight(&self) -> Highlight {
+        self.highlight
     }
 
+    // SETTER //////////////////////////////////////////////////////////////////////
     pub fn set_radius(&mut self, radius: f32) -> &mut Self {
         match self.drawable {
             Drawables::Circle(ref mut circle) => circle.set_radius(radius),
@@ -66,11 +59,16 @@ impl Node {
 
     pub fn set_drawable_image(
         &mut self,
-        pos: egui::Pos2,
         size: egui::Vec2,
         texture_id: egui::TextureId,
     ) -> &mut Self {
-        self.drawable = Drawables::Image(Image::new(pos, size, texture_id));
+        self.drawable = Drawables::Image(Image::new(size, texture_id));
+        self
+    }
+
+    /// Draw the node as a circle again after it was drawn with an image
+    pub fn set_drawable_circle(&mut self, stroke: egui::Stroke) -> &mut Self {
+        self.drawable = Drawables::Circle(Circle::new(stroke));
         self
     }
 
@@ -96,6 +94,10 @@ impl Node {
         };
     }
 
+    pub fn set_highlight(&mut self, highlight: Highlight) {
+        self.highlight = highlight;
+    }
+
     pub fn set_is_dragging(&mut self, is_dragging: bool) {
         self.is_dragging = is_dragging;
     }
@@ -104,31 +106,89 @@ impl Node {
         self.drag_start = drag_start;
     }
 
-    pub fn contains(&self, pos: egui::Pos2) -> bool {
+    /// Checks if a point is on the node drawn at `pos`
+    pub fn contains(&self, pos: egui::Pos2, point: egui::Pos2) -> bool {
+        match self.drawable {
+            Drawables::Circle(circle) => circle.contains(pos, point),
+            Drawables::Image(image) => image.contains(pos, point),
+        }
+    }
+
+    // SAVE ////////////////////////////////////////////////////////////////////////
+    pub fn to_data(self, pos: (f32, f32), textures: &(impl TextureNames + ?Sized)) -> NodeData {
+        let style = match self.drawable {
+            Drawables::Circle(circle) => NodeStyle::Circle {
+                radius: circle.radius(),
+                stroke: circle.stroke().into(),
+            },
+            Drawables::Image(image) => NodeStyle::Image {
+                size: [image.size().x, image.size().y],
+                // an unknown texture is saved with a name that will not be found
+                texture: textures
+                    .name(image.texture_id())
+                    .unwrap_or_else(|| format!("{:?}", image.texture_id())),
+            },
+        };
+        NodeData {
+            id: self.id,
+            pos: [pos.0, pos.1],
+            style,
+        }
+    }
+
+    /// Node saved in `data`, drawn as a circle if its texture is not found
+    pub fn from_data(data: &NodeData, textures: &(impl TextureNames + ?Sized)) -> Self {
+        let mut node =
+            Node::new_circle_node(Some(data.id), egui::Stroke::new(1.0, egui::Color32::RED));
+        match &data.style {
+            NodeStyle::Circle { radius, stroke } => {
+                node.set_stroke((*stroke).into()).set_radius(*radius);
+            }
+            NodeStyle::Image { size, texture } => {
+                if let Some(texture_id) = textures.texture_id(texture) {
+                    node.set_drawable_image(egui::Vec2::new(size[0], size[1]), texture_id);
+                }
+            }
+        }
+        node
+    }
+
+    /// Copy of the node with its sizes multiplied by `zoom`
+    pub fn scaled(mut self, zoom: f32) -> Self {
         match self.drawable {
-            Drawables::Circle(circle) => circle.contains(pos),
-            Drawables::Image(image) => image.contains(pos),
+            Drawables::Circle(ref mut circle) => {
+                let stroke = circle.stroke();
+                circle.set_radius(circle.radius() * zoom);
+                circle.set_width(stroke.width * zoom);
+            }
+            Drawables::Image(ref mut image) => {
+                *image = Image::new(image.size() * zoom, image.texture_id());
+            }
         }
+        self
     }
 
-    pub fn follow_mouse(&mut self, event: &egui::Event) {
-        match &mut self.drawable {
+    /// Draw a shape a bit bigger than the node drawn at `pos` to show that it is selected
+    pub fn draw_halo(&self, ui: &mut egui::Ui, pos: egui::Pos2, color: egui::Color32) {
+        match self.drawable {
             Drawables::Circle(circle) => {
-                circle.follow_mouse(event);
+                let radius = circle.radius() + circle.stroke().width / 2. + 4.;
+                ui.painter().circle_filled(pos, radius, color);
             }
             Drawables::Image(image) => {
-                image.follow_mouse(event);
+                ui.painter()
+                    .rect_filled(image.rect(pos).expand(4.), 4., color);
             }
         }
     }
 
-    pub fn draw(&self, ui: &mut egui::Ui) {
+    pub fn draw(&self, ui: &mut egui::Ui, pos: egui::Pos2) {
         match self.drawable {
             Drawables::Circle(circle) => {
-                circle.draw(ui);
+                circle.draw(ui, pos);
             }
             Drawables::Image(image) => {
-                image.draw(ui);
+                image.draw(ui, pos);
             }
         }
     }
@@ -141,15 +201,3 @@ impl std::fmt::Display for Node {
         Ok(())
     }
 }
-
-pub fn pos2_to_node(id: usize, pos: egui::Pos2, stroke: Option<egui::Stroke>) -> Node {
-    Node {
-        id,
-        is_dragging: false,
-        drag_start: egui::Pos2::ZERO,
-        drawable: Drawables::Circle(Circle::new(
-            pos,
-            stroke.unwrap_or(egui::Stroke::new(1.0, egui::Color32::RED)),
-        )),
-    }
-}
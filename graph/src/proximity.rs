@@ -0,0 +1,241 @@
+use crate::delaunay::delaunay_edges;
+use serde::{Deserialize, Serialize};
+
+/// Graph whose edges only depend on the positions of the nodes
+#[derive(Default, Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ProximityGraph {
+    /// Triangulation where no circumcircle of a triangle contains a node
+    Delaunay,
+    /// Edges whose diametral circle contains no other node
+    #[default]
+    Gabriel,
+    /// Edges with no other node closer to both of its nodes than they are to each other
+    RelativeNeighbourhood,
+    /// Every node joined to its `k` nearest nodes
+    KNearest { k: usize },
+    /// Every pair of nodes at most `radius` apart
+    UnitDisk { radius: f32 },
+}
+
+impl ProximityGraph {
+    /// Every proximity graph with a default parameter
+    pub const ALL: [ProximityGraph; 5] = [
+        ProximityGraph::Delaunay,
+        ProximityGraph::Gabriel,
+        ProximityGraph::RelativeNeighbourhood,
+        ProximityGraph::KNearest { k: 3 },
+        ProximityGraph::UnitDisk { radius: 150. },
+    ];
+
+    /// Checks if the two graphs are built the same way, whatever their parameter
+    pub fn same_kind(&self, other: &ProximityGraph) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Edges between the points, every one once with its smallest index first
+    pub fn edges(&self, points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+        match *self {
+            ProximityGraph::Delaunay => delaunay_edges(points),
+            ProximityGraph::Gabriel => gabriel_edges(points),
+            ProximityGraph::RelativeNeighbourhood => relative_neighbourhood_edges(points),
+            ProximityGraph::KNearest { k } => k_nearest_edges(points, k),
+            ProximityGraph::UnitDisk { radius } => unit_disk_edges(points, radius),
+        }
+    }
+}
+
+impl std::fmt::Display for ProximityGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProximityGraph::Delaunay => write!(f, "Delaunay")?,
+            ProximityGraph::Gabriel => write!(f, "Gabriel")?,
+            ProximityGraph::RelativeNeighbourhood => write!(f, "Relative neighbourhood")?,
+            ProximityGraph::KNearest { .. } => write!(f, "k nearest neighbours")?,
+            ProximityGraph::UnitDisk { .. } => write!(f, "Unit disk")?,
+        }
+        Ok(())
+    }
+}
+
+fn distance2(points: &[(f32, f32)], i: usize, j: usize) -> f32 {
+    let ((xi, yi), (xj, yj)) = (points[i], points[j]);
+    (xj - xi).powi(2) + (yj - yi).powi(2)
+}
+
+/// Edges of the Delaunay triangulation whose diametral circle contains no other point
+pub fn gabriel_edges(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+    delaunay_edges(points)
+        .into_iter()
+        .filter(|&(i, j)| {
+            let center = (
+                (points[i].0 + points[j].0) / 2.,
+                (points[i].1 + points[j].1) / 2.,
+            );
+            let radius2 = distance2(points, i, j) / 4.;
+            points.iter().enumerate().all(|(k, &(x, y))| {
+                k == i || k == j || (x - center.0).powi(2) + (y - center.1).powi(2) >= radius2
+            })
+        })
+        .collect()
+}
+
+/// Edges of the Gabriel graph with no point closer to both of their ends
+/// than the ends are to each other
+pub fn relative_neighbourhood_edges(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+    gabriel_edges(points)
+        .into_iter()
+        .filter(|&(i, j)| {
+            let length2 = distance2(points, i, j);
+            (0..points.len()).all(|k| {
+                k == i || k == j || distance2(points, i, k).max(distance2(points, j, k)) >= length2
+            })
+        })
+        .collect()
+}
+
+/// Every point joined to its `k` nearest points (so a point can have more than `k` edges)
+pub fn k_nearest_edges(points: &[(f32, f32)], k: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for i in 0..points.len() {
+        let mut others: Vec<usize> = (0..points.len()).filter(|&j| j != i).collect();
+        let k = k.min(others.len());
+        if k == 0 {
+            continue;
+        }
+        others.select_nth_unstable_by(k - 1, |&j1, &j2| {
+            distance2(points, i, j1).total_cmp(&distance2(points, i, j2))
+        });
+        edges.extend(others[..k].iter().map(|&j| (i.min(j), i.max(j))));
+    }
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// Every pair of points at most `radius` apart
+pub fn unit_disk_edges(points: &[(f32, f32)], radius: f32) -> Vec<(usize, usize)> {
+    // only the points close enough on the x axis are compared
+    let mut sorted: Vec<usize> = (0..points.len()).collect();
+    sorted.sort_by(|&i, &j| points[i].0.total_cmp(&points[j].0));
+    let mut edges = Vec::new();
+    for (a, &i) in sorted.iter().enumerate() {
+        for &j in sorted[a + 1..].iter() {
+            if points[j].0 - points[i].0 > radius {
+                break;
+            }
+            if distance2(points, i, j) <= radius * radius {
+                edges.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+    edges.sort_unstable();
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delaunay::euclidean_mst;
+    use crate::generator::test_inputs::{pairs, random_points};
+    use crate::generator::Rng;
+
+    fn brute_force_gabriel(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+        pairs(points.len())
+            .filter(|&(i, j)| {
+                (0..points.len()).all(|k| {
+                    k == i
+                        || k == j
+                        || distance2(points, i, k) + distance2(points, j, k)
+                            >= distance2(points, i, j)
+                })
+            })
+            .collect()
+    }
+
+    fn brute_force_relative_neighbourhood(points: &[(f32, f32)]) -> Vec<(usize, usize)> {
+        pairs(points.len())
+            .filter(|&(i, j)| {
+                (0..points.len()).all(|k| {
+                    k == i
+                        || k == j
+                        || distance2(points, i, k).max(distance2(points, j, k))
+                            >= distance2(points, i, j)
+                })
+            })
+            .collect()
+    }
+
+    fn brute_force_k_nearest(points: &[(f32, f32)], k: usize) -> Vec<(usize, usize)> {
+        // the random points have no ties, the k nearest are the ones with fewer than k closer
+        pairs(points.len())
+            .filter(|&(i, j)| {
+                let closer = |i: usize, j: usize| {
+                    (0..points.len())
+                        .filter(|&l| l != i && distance2(points, i, l) < distance2(points, i, j))
+                        .count()
+                };
+                closer(i, j) < k || closer(j, i) < k
+            })
+            .collect()
+    }
+
+    fn brute_force_unit_disk(points: &[(f32, f32)], radius: f32) -> Vec<(usize, usize)> {
+        pairs(points.len())
+            .filter(|&(i, j)| distance2(points, i, j) <= radius * radius)
+            .collect()
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        let mut rng = Rng::new(10);
+        for n in 0..40 {
+            let points = random_points(&mut rng, n, 1000.);
+            assert_eq!(gabriel_edges(&points), brute_force_gabriel(&points));
+            assert_eq!(
+                relative_neighbourhood_edges(&points),
+                brute_force_relative_neighbourhood(&points)
+            );
+            for k in [0, 1, 3, 6] {
+                assert_eq!(
+                    k_nearest_edges(&points, k),
+                    brute_force_k_nearest(&points, k)
+                );
+            }
+            for radius in [0., 100., 300., 2000.] {
+                assert_eq!(
+                    unit_disk_edges(&points, radius),
+                    brute_force_unit_disk(&points, radius)
+                );
+            }
+        }
+    }
+
+    fn is_subset(edges: &[(usize, usize)], of: &[(usize, usize)]) -> bool {
+        edges.iter().all(|edge| of.contains(edge))
+    }
+
+    #[test]
+    fn each_graph_contains_the_previous_one() {
+        let mut rng = Rng::new(11);
+        let grid: Vec<(f32, f32)> = (0..25)
+            .map(|i| ((i % 5) as f32 * 100., (i / 5) as f32 * 100.))
+            .collect();
+        let line: Vec<(f32, f32)> = (0..10).map(|i| (i as f32 * 30., 50.)).collect();
+        let sets = (0..30)
+            .map(|n| random_points(&mut rng, n, 1000.))
+            .chain([grid, line]);
+        for points in sets {
+            let mut emst: Vec<(usize, usize)> = euclidean_mst(&points)
+                .into_iter()
+                .map(|(i, j)| (i.min(j), i.max(j)))
+                .collect();
+            emst.sort_unstable();
+            let relative_neighbourhood = relative_neighbourhood_edges(&points);
+            let gabriel = gabriel_edges(&points);
+            let delaunay = delaunay_edges(&points);
+            assert!(is_subset(&emst, &relative_neighbourhood), "{points:?}");
+            assert!(is_subset(&relative_neighbourhood, &gabriel), "{points:?}");
+            assert!(is_subset(&gabriel, &delaunay), "{points:?}");
+        }
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(feature = "gui")]
+use crate::graph::Graph;
+use crate::weighting::Weighting;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Stroke saved as a width and an RGBA color
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StrokeData {
+    pub width: f32,
+    pub color: [u8; 4],
+}
+
+#[cfg(feature = "gui")]
+impl From<egui::Stroke> for StrokeData {
+    fn from(stroke: egui::Stroke) -> Self {
+        Self {
+            width: stroke.width,
+            color: stroke.color.to_array(),
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl From<StrokeData> for egui::Stroke {
+    fn from(stroke: StrokeData) -> Self {
+        let [r, g, b, a] = stroke.color;
+        egui::Stroke::new(
+            stroke.width,
+            egui::Color32::from_rgba_premultiplied(r, g, b, a),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NodeStyle {
+    Circle { radius: f32, stroke: StrokeData },
+    Image { size: [f32; 2], texture: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeData {
+    pub id: usize,
+    pub pos: [f32; 2],
+    pub style: NodeStyle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VertexStyle {
+    Straight(StrokeData),
+    Cobblestone { textures: Vec<String>, width: f32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VertexData {
+    pub node_id1: usize,
+    pub node_id2: usize,
+    #[serde(default)]
+    pub weight: Option<f32>,
+    pub style: VertexStyle,
+}
+
+/// Graph as it is saved in a file
+/// the textures are saved by their name since `egui::TextureId` changes between runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphData {
+    pub nodes: Vec<NodeData>,
+    pub adjacencies: Vec<Vec<usize>>,
+    pub vertices: Vec<VertexData>,
+    #[serde(default)]
+    pub weighting: Weighting,
+}
+
+/// Names of the textures used by a graph
+#[cfg(feature = "gui")]
+pub trait TextureNames {
+    fn name(&self, texture_id: egui::TextureId) -> Option<String>;
+    fn texture_id(&self, name: &str) -> Option<egui::TextureId>;
+}
+
+/// Textures given as `(name, texture id)` pairs
+#[cfg(feature = "gui")]
+impl TextureNames for [(String, egui::TextureId)] {
+    fn name(&self, texture_id: egui::TextureId) -> Option<String> {
+        self.iter()
+            .find(|(_, id)| *id == texture_id)
+            .map(|(name, _)| name.clone())
+    }
+
+    fn texture_id(&self, name: &str) -> Option<egui::TextureId> {
+        self.iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, texture_id)| *texture_id)
+    }
+}
+
+impl GraphData {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Read a graph from JSON and check that its vertices and adjacencies agree
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let data: GraphData = serde_json::from_str(json)?;
+        data.check().map_err(serde::de::Error::custom)?;
+        Ok(data)
+    }
+
+    /// Checks that the vertices and adjacencies only refer to the nodes of the graph,
+    /// that every pair of adjacent nodes has exactly one vertex between two different nodes
+    /// and that the weights are finite and not negative
+    pub fn check(&self) -> Result<(), String> {
+        let n_nodes = self.nodes.len();
+        if self.adjacencies.len() != n_nodes {
+            return Err(format!(
+                "{} adjacency lists for {n_nodes} nodes",
+                self.adjacencies.len()
+            ));
+        }
+        // the adjacencies and vertices refer to the ids of the nodes
+        let mut indices = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if indices.insert(node.id, index).is_some() {
+                return Err(format!("several nodes with the id {}", node.id));
+            }
+        }
+        let adjacent = |id1: usize, id2: usize| {
+            indices
+                .get(&id1)
+                .map_or(false, |&index| self.adjacencies[index].contains(&id2))
+        };
+        // every vertex is between two different adjacent nodes, once
+        let mut vertices = HashSet::new();
+        for vertex in self.vertices.iter() {
+            let (id1, id2) = (vertex.node_id1, vertex.node_id2);
+            if id1 == id2 {
+                return Err(format!("vertex between {id1} and itself"));
+            }
+            if !adjacent(id1, id2) || !adjacent(id2, id1) {
+                return Err(format!("invalid vertex between {id1} and {id2}"));
+            }
+            if !vertices.insert((id1.min(id2), id1.max(id2))) {
+                return Err(format!("several vertices between {id1} and {id2}"));
+            }
+            if let Some(weight) = vertex.weight.filter(|w| !w.is_finite() || *w < 0.) {
+                return Err(format!(
+                    "invalid weight {weight} of the vertex between {id1} and {id2}"
+                ));
+            }
+        }
+        // and every adjacency has its vertex
+        for (node, adjacency) in self.nodes.iter().zip(self.adjacencies.iter()) {
+            for (i, &adj) in adjacency.iter().enumerate() {
+                if !adjacent(adj, node.id) {
+                    return Err(format!("invalid adjacency between {} and {adj}", node.id));
+                }
+                if adjacency[..i].contains(&adj) {
+                    return Err(format!("several adjacencies between {} and {adj}", node.id));
+                }
+                if !vertices.contains(&(node.id.min(adj), node.id.max(adj))) {
+                    return Err(format!("no vertex between {} and {adj}", node.id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Graph {
+    /// Save the graph as JSON
+    pub fn to_json(&self, textures: &(impl TextureNames + ?Sized)) -> serde_json::Result<String> {
+        self.to_data(textures).to_json()
+    }
+
+    /// Load a graph saved as JSON
+    pub fn from_json(
+        json: &str,
+        textures: &(impl TextureNames + ?Sized),
+    ) -> serde_json::Result<Graph> {
+        Ok(Graph::from_data(GraphData::from_json(json)?, textures))
+    }
+}
@@ -0,0 +1,368 @@
+use crate::spanning_tree::WeightedEdge;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Shortest path found between two nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    /// Nodes of the path from the source to the destination
+    pub nodes: Vec<usize>,
+    /// Indices of the edges of the path, in the same order
+    pub edges: Vec<usize>,
+    /// Sum of the weights of the edges
+    pub length: f32,
+    /// Number of nodes whose distance was settled by the search
+    pub expanded: usize,
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShortestPathAlgorithm {
+    #[default]
+    Dijkstra,
+    /// Dijkstra guided by an estimation of the distance left to the destination
+    AStar,
+}
+
+impl ShortestPathAlgorithm {
+    pub const ALL: [ShortestPathAlgorithm; 2] = [
+        ShortestPathAlgorithm::Dijkstra,
+        ShortestPathAlgorithm::AStar,
+    ];
+
+    /// Shortest path between two nodes of the graph with `n_nodes` nodes,
+    /// `heuristic` is only used by A* (see `a_star`)
+    pub fn run(
+        &self,
+        n_nodes: usize,
+        edges: &[WeightedEdge],
+        source: usize,
+        destination: usize,
+        heuristic: impl Fn(usize) -> f32,
+    ) -> Option<Path> {
+        match self {
+            ShortestPathAlgorithm::Dijkstra => dijkstra(n_nodes, edges, source, destination),
+            ShortestPathAlgorithm::AStar => a_star(n_nodes, edges, source, destination, heuristic),
+        }
+    }
+}
+
+impl std::fmt::Display for ShortestPathAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShortestPathAlgorithm::Dijkstra => write!(f, "Dijkstra")?,
+            ShortestPathAlgorithm::AStar => write!(f, "A*")?,
+        }
+        Ok(())
+    }
+}
+
+/// Node waiting in the heap of the search with its estimated length
+/// the order is reversed so that the `BinaryHeap` gives the shortest first
+#[derive(Debug, Clone, Copy)]
+struct HeapNode {
+    estimate: f32,
+    node: usize,
+}
+
+impl PartialEq for HeapNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapNode {}
+
+impl PartialOrd for HeapNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then(other.node.cmp(&self.node))
+    }
+}
+
+/// Settle the nodes by increasing distance from the source until the destination
+/// the weights must not be negative
+pub fn dijkstra(
+    n_nodes: usize,
+    edges: &[WeightedEdge],
+    source: usize,
+    destination: usize,
+) -> Option<Path> {
+    a_star(n_nodes, edges, source, destination, |_| 0.)
+}
+
+/// Dijkstra where a node is settled by its distance plus `heuristic(node)`,
+/// an estimation of its distance to the destination that must never be too big
+pub fn a_star(
+    n_nodes: usize,
+    edges: &[WeightedEdge],
+    source: usize,
+    destination: usize,
+    heuristic: impl Fn(usize) -> f32,
+) -> Option<Path> {
+    if source >= n_nodes || destination >= n_nodes {
+        return None;
+    }
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); n_nodes];
+    for (e, &(id1, id2, _)) in edges.iter().enumerate() {
+        incident[id1].push(e);
+        incident[id2].push(e);
+    }
+
+    let mut distances = vec![f32::INFINITY; n_nodes];
+    // edge through which every node was reached
+    let mut previous: Vec<Option<usize>> = vec![None; n_nodes];
+    let mut settled = vec![false; n_nodes];
+    let mut expanded = 0;
+    let mut heap = BinaryHeap::new();
+    distances[source] = 0.;
+    heap.push(HeapNode {
+        estimate: heuristic(source),
+        node: source,
+    });
+
+    while let Some(HeapNode { node, .. }) = heap.pop() {
+        if settled[node] {
+            continue;
+        }
+        settled[node] = true;
+        expanded += 1;
+        if node == destination {
+            break;
+        }
+        for &e in incident[node].iter() {
+            let (id1, id2, weight) = edges[e];
+            let other = if id1 == node { id2 } else { id1 };
+            let distance = distances[node] + weight;
+            if !settled[other] && distance < distances[other] {
+                distances[other] = distance;
+                previous[other] = Some(e);
+                heap.push(HeapNode {
+                    estimate: distance + heuristic(other),
+                    node: other,
+                });
+            }
+        }
+    }
+    if !settled[destination] {
+        return None;
+    }
+
+    // walk back from the destination to the source
+    let mut nodes = vec![destination];
+    let mut path_edges = Vec::new();
+    let mut current = destination;
+    while let Some(e) = previous[current] {
+        let (id1, id2, _) = edges[e];
+        current = if id1 == current { id2 } else { id1 };
+        path_edges.push(e);
+        nodes.push(current);
+    }
+    nodes.reverse();
+    path_edges.reverse();
+    Some(Path {
+        nodes,
+        edges: path_edges,
+        length: distances[destination],
+        expanded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::test_inputs::{random_edges, random_points};
+    use crate::generator::Rng;
+    use crate::model::Model;
+    use crate::weighting::Weighting;
+
+    /// Length of the shortest path from the source to every node, by relaxing every edge
+    /// until nothing changes (Bellman–Ford)
+    fn brute_force_distances(n_nodes: usize, edges: &[WeightedEdge], source: usize) -> Vec<f32> {
+        let mut distances = vec![f32::INFINITY; n_nodes];
+        distances[source] = 0.;
+        for _ in 0..n_nodes {
+            for &(i, j, weight) in edges {
+                distances[i] = distances[i].min(distances[j] + weight);
+                distances[j] = distances[j].min(distances[i] + weight);
+            }
+        }
+        distances
+    }
+
+    /// The path goes from the source to the destination through its edges
+    /// and its length is the sum of their weights
+    fn assert_valid(edges: &[WeightedEdge], path: &Path, source: usize, destination: usize) {
+        assert_eq!(path.nodes.first(), Some(&source));
+        assert_eq!(path.nodes.last(), Some(&destination));
+        assert_eq!(path.edges.len() + 1, path.nodes.len());
+        for (k, &e) in path.edges.iter().enumerate() {
+            let (i, j, _) = edges[e];
+            let (from, to) = (path.nodes[k], path.nodes[k + 1]);
+            assert!((i, j) == (from, to) || (j, i) == (from, to), "{path:?}");
+        }
+        let length: f32 = path.edges.iter().map(|&e| edges[e].2).sum();
+        assert!((length - path.length).abs() <= 1e-3, "{path:?}");
+    }
+
+    /// Every algorithm finds a path as short as the brute force one, or no path when
+    /// the destination cannot be reached; A* is guided by half the distance left
+    fn assert_shortest(n_nodes: usize, edges: &[WeightedEdge]) {
+        for destination in 0..n_nodes {
+            let distances = brute_force_distances(n_nodes, edges, destination);
+            let heuristic = |node: usize| distances[node] / 2.;
+            for (source, &distance) in distances.iter().enumerate() {
+                for algorithm in ShortestPathAlgorithm::ALL {
+                    let path = algorithm.run(n_nodes, edges, source, destination, heuristic);
+                    let Some(path) = path else {
+                        assert!(distance.is_infinite(), "{algorithm}");
+                        continue;
+                    };
+                    assert_valid(edges, &path, source, destination);
+                    assert!(
+                        (path.length - distance).abs() <= 1e-3,
+                        "{algorithm}: {} instead of {distance}",
+                        path.length,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_as_brute_force_on_random_graphs() {
+        let mut rng = Rng::new(12);
+        for n_nodes in 1..15 {
+            let edges = random_edges(&mut rng, n_nodes, n_nodes, false);
+            assert_shortest(n_nodes, &edges);
+            let edges = random_edges(&mut rng, n_nodes, 2 * n_nodes, true);
+            assert_shortest(n_nodes, &edges);
+        }
+    }
+
+    #[test]
+    fn zero_cost_roads() {
+        // 0 - 1 - 2 is free, 0 - 2 is not
+        let edges = [(0, 1, 0.), (1, 2, 0.), (0, 2, 1.), (2, 3, 0.)];
+        assert_shortest(4, &edges);
+        let path = dijkstra(4, &edges, 0, 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(path.length, 0.);
+    }
+
+    #[test]
+    fn from_a_node_to_itself_or_to_nowhere() {
+        let edges = [(0, 1, 2.), (2, 3, 1.)];
+        for algorithm in ShortestPathAlgorithm::ALL {
+            let path = algorithm.run(4, &edges, 1, 1, |_| 0.).unwrap();
+            assert_eq!(path.nodes, vec![1]);
+            assert!(path.edges.is_empty());
+            assert_eq!(path.length, 0.);
+            assert_eq!(path.expanded, 1);
+
+            assert_eq!(algorithm.run(4, &edges, 0, 3, |_| 0.), None);
+            assert_eq!(algorithm.run(4, &edges, 0, 4, |_| 0.), None);
+            assert_eq!(algorithm.run(0, &[], 0, 0, |_| 0.), None);
+        }
+    }
+
+    /// Model of random points joined by random edges, some of them with an explicit weight
+    /// much longer than their length, and some free roads when `free` is true
+    fn random_model(rng: &mut Rng, n_nodes: usize, free: bool) -> Model {
+        let mut model = Model::default();
+        for pos in random_points(rng, n_nodes, 1000.) {
+            model.add_node(pos);
+        }
+        for (i, j, weight) in random_edges(rng, n_nodes, 2 * n_nodes, n_nodes % 2 == 0) {
+            if model.add_edge(i, j) {
+                match rng.index(3) {
+                    0 => {}
+                    1 if free => {
+                        model.set_weight(i, j, Some(0.));
+                    }
+                    _ => {
+                        model.set_weight(i, j, Some(weight * 20.));
+                    }
+                }
+            }
+        }
+        model
+    }
+
+    #[test]
+    fn a_star_is_exact_on_models_with_both_weightings() {
+        let mut rng = Rng::new(13);
+        for n_nodes in 1..25 {
+            let mut model = random_model(&mut rng, n_nodes, n_nodes % 3 == 0);
+            for weighting in Weighting::ALL {
+                model.set_weighting(weighting);
+                let edges = model.indexed_edges();
+                for destination in 0..n_nodes {
+                    let distances = brute_force_distances(n_nodes, &edges, destination);
+                    for (source, &distance) in distances.iter().enumerate() {
+                        let dijkstra = model.shortest_path(
+                            ShortestPathAlgorithm::Dijkstra,
+                            source,
+                            destination,
+                        );
+                        let a_star =
+                            model.shortest_path(ShortestPathAlgorithm::AStar, source, destination);
+                        match (dijkstra, a_star) {
+                            (Some(dijkstra), Some(a_star)) => {
+                                assert!((dijkstra.length - distance).abs() <= 1e-2);
+                                assert!(
+                                    (a_star.length - dijkstra.length).abs() <= 1e-2,
+                                    "{weighting}: {} instead of {}",
+                                    a_star.length,
+                                    dijkstra.length
+                                );
+                            }
+                            (None, None) => assert!(distance.is_infinite()),
+                            paths => panic!("{weighting}: {paths:?}"),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_star_expands_fewer_nodes_on_a_line() {
+        // the straight line is the exact length of the path along a line of nodes
+        let mut model: Model = Model::default();
+        for i in 0..20 {
+            model.add_node((i as f32 * 10., 0.));
+        }
+        for i in 1..20 {
+            model.add_edge(i - 1, i);
+        }
+        // a branch going away from the destination
+        for i in 0..10 {
+            let id = model.add_node((100., 10. * (i + 1) as f32));
+            model.add_edge(if i == 0 { 10 } else { id - 1 }, id);
+        }
+        // with explicit weights, the straight line is scaled by the smallest weight per length
+        for (id1, id2, _) in model.weighted_edges() {
+            model.set_weight(id1, id2, Some(200.));
+        }
+        for weighting in Weighting::ALL {
+            model.set_weighting(weighting);
+            let dijkstra = model
+                .shortest_path(ShortestPathAlgorithm::Dijkstra, 10, 19)
+                .unwrap();
+            let a_star = model
+                .shortest_path(ShortestPathAlgorithm::AStar, 10, 19)
+                .unwrap();
+            assert_eq!(a_star.nodes, dijkstra.nodes);
+            assert_eq!(a_star.expanded, 10, "{weighting}");
+            assert!(dijkstra.expanded > a_star.expanded);
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use crate::components::Components;
+use crate::union_find::UnionFind;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Weighted edge `(node_id1, node_id2, weight)` given to the algorithms
+pub type WeightedEdge = (usize, usize, f32);
+
+/// What happened to an edge during one step of an algorithm
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StepKind {
+    Considered,
+    Accepted,
+    /// Rejected because both ends are already connected (it closes a cycle)
+    Rejected,
+}
+
+/// One step of the trace of a spanning tree algorithm
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Index of the edge in the edges given to the algorithm
+    pub edge: usize,
+    pub kind: StepKind,
+}
+
+/// Number of components of the graph with `n_nodes` nodes after the steps of its trace,
+/// every accepted edge joins two of them
+pub fn n_components(n_nodes: usize, steps: &[Step]) -> usize {
+    let accepted = steps
+        .iter()
+        .filter(|step| step.kind == StepKind::Accepted)
+        .count();
+    n_nodes - accepted
+}
+
+/// Minimum spanning forest: a spanning tree for every connected component
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningForest {
+    /// Indices of the edges of the forest, in the edges given to the algorithm
+    pub edges: Vec<usize>,
+    pub components: Components,
+    /// Weight of the tree of every component
+    pub totals: Vec<f32>,
+}
+
+impl SpanningForest {
+    /// Forest made of the edges `forest` of a graph with `n_nodes` nodes
+    pub fn new(n_nodes: usize, edges: &[WeightedEdge], forest: Vec<usize>) -> Self {
+        let components = Components::new(n_nodes, edges);
+        let mut totals = vec![0.; components.count()];
+        for &e in forest.iter() {
+            let (id1, _, weight) = edges[e];
+            totals[components.label(id1)] += weight;
+        }
+        Self {
+            edges: forest,
+            components,
+            totals,
+        }
+    }
+
+    /// Sum of the weights of every tree
+    pub fn total(&self) -> f32 {
+        self.totals.iter().sum()
+    }
+
+    /// Checks if the forest is a single tree spanning every node
+    pub fn is_tree(&self) -> bool {
+        self.components.is_connected()
+    }
+}
+
+impl std::fmt::Display for StepKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepKind::Considered => write!(f, "Considered")?,
+            StepKind::Accepted => write!(f, "Accepted")?,
+            StepKind::Rejected => write!(f, "Rejected (cycle)")?,
+        }
+        Ok(())
+    }
+}
+
+/// Trace recorded while running an algorithm, nothing is recorded when it is `None`
+type Trace<'a> = Option<&'a mut Vec<Step>>;
+
+fn record(trace: &mut Trace<'_>, edge: usize, kind: StepKind) {
+    if let Some(steps) = trace.as_mut() {
+        steps.push(Step { edge, kind });
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum SpanningTreeAlgorithm {
+    #[default]
+    Kruskal,
+    Prim,
+    Boruvka,
+}
+
+impl SpanningTreeAlgorithm {
+    pub const ALL: [SpanningTreeAlgorithm; 3] = [
+        SpanningTreeAlgorithm::Kruskal,
+        SpanningTreeAlgorithm::Prim,
+        SpanningTreeAlgorithm::Boruvka,
+    ];
+
+    /// Returns the indices (in `edges`) of the edges of a minimum spanning forest
+    /// of the graph with `n_nodes` nodes
+    pub fn run(&self, n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+        match self {
+            SpanningTreeAlgorithm::Kruskal => kruskal(n_nodes, edges),
+            SpanningTreeAlgorithm::Prim => prim(n_nodes, edges),
+            SpanningTreeAlgorithm::Boruvka => boruvka(n_nodes, edges),
+        }
+    }
+
+    /// Runs the algorithm and returns every step it went through
+    pub fn trace(&self, n_nodes: usize, edges: &[WeightedEdge]) -> Vec<Step> {
+        let mut steps = Vec::new();
+        match self {
+            SpanningTreeAlgorithm::Kruskal => kruskal_(n_nodes, edges, Some(&mut steps)),
+            SpanningTreeAlgorithm::Prim => prim_(n_nodes, edges, Some(&mut steps)),
+            SpanningTreeAlgorithm::Boruvka => boruvka_(n_nodes, edges, Some(&mut steps)),
+        };
+        steps
+    }
+}
+
+impl std::fmt::Display for SpanningTreeAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpanningTreeAlgorithm::Kruskal => write!(f, "Kruskal")?,
+            SpanningTreeAlgorithm::Prim => write!(f, "Prim")?,
+            SpanningTreeAlgorithm::Boruvka => write!(f, "Borůvka")?,
+        }
+        Ok(())
+    }
+}
+
+/// Total order on the edges: by weight then by index to break ties
+fn compare_edges(edges: &[WeightedEdge], e1: usize, e2: usize) -> Ordering {
+    edges[e1].2.total_cmp(&edges[e2].2).then(e1.cmp(&e2))
+}
+
+/// Sort the edges by weight and add them if they don't close a cycle
+pub fn kruskal(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+    kruskal_(n_nodes, edges, None)
+}
+
+fn kruskal_(n_nodes: usize, edges: &[WeightedEdge], mut trace: Trace<'_>) -> Vec<usize> {
+    let mut ordered: Vec<usize> = (0..edges.len()).collect();
+    ordered.sort_by(|&e1, &e2| compare_edges(edges, e1, e2));
+
+    let mut union_find = UnionFind::new(n_nodes);
+    let mut tree = Vec::new();
+    for e in ordered {
+        let (id1, id2, _) = edges[e];
+        record(&mut trace, e, StepKind::Considered);
+        if union_find.union(id1, id2) {
+            tree.push(e);
+            record(&mut trace, e, StepKind::Accepted);
+        } else {
+            record(&mut trace, e, StepKind::Rejected);
+        }
+    }
+    tree
+}
+
+/// Edge waiting in the heap of Prim's algorithm
+/// the order is reversed so that the `BinaryHeap` gives the lightest edge first
+#[derive(Debug, Clone, Copy)]
+struct HeapEdge {
+    weight: f32,
+    edge: usize,
+    to: usize,
+}
+
+impl PartialEq for HeapEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEdge {}
+
+impl PartialOrd for HeapEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .weight
+            .total_cmp(&self.weight)
+            .then(other.edge.cmp(&self.edge))
+    }
+}
+
+/// Grow a tree from a node by always adding the lightest edge leaving it
+/// restart from every node not reached yet to get a forest
+pub fn prim(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+    prim_(n_nodes, edges, None)
+}
+
+fn prim_(n_nodes: usize, edges: &[WeightedEdge], mut trace: Trace<'_>) -> Vec<usize> {
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); n_nodes];
+    for (e, &(id1, id2, _)) in edges.iter().enumerate() {
+        incident[id1].push(e);
+        incident[id2].push(e);
+    }
+
+    let mut in_tree = vec![false; n_nodes];
+    let mut tree = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for root in 0..n_nodes {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        push_incident(root, &incident, edges, &in_tree, &mut heap);
+
+        while let Some(HeapEdge { edge, to, .. }) = heap.pop() {
+            record(&mut trace, edge, StepKind::Considered);
+            if in_tree[to] {
+                record(&mut trace, edge, StepKind::Rejected);
+                continue;
+            }
+            in_tree[to] = true;
+            tree.push(edge);
+            record(&mut trace, edge, StepKind::Accepted);
+            push_incident(to, &incident, edges, &in_tree, &mut heap);
+        }
+    }
+    tree
+}
+
+fn push_incident(
+    node: usize,
+    incident: &[Vec<usize>],
+    edges: &[WeightedEdge],
+    in_tree: &[bool],
+    heap: &mut BinaryHeap<HeapEdge>,
+) {
+    for &e in incident[node].iter() {
+        let (id1, id2, weight) = edges[e];
+        let to = if id1 == node { id2 } else { id1 };
+        if !in_tree[to] {
+            heap.push(HeapEdge {
+                weight,
+                edge: e,
+                to,
+            });
+        }
+    }
+}
+
+/// Every component picks its lightest outgoing edge, then the components are merged
+/// until no component has an outgoing edge
+pub fn boruvka(n_nodes: usize, edges: &[WeightedEdge]) -> Vec<usize> {
+    boruvka_(n_nodes, edges, None)
+}
+
+fn boruvka_(n_nodes: usize, edges: &[WeightedEdge], mut trace: Trace<'_>) -> Vec<usize> {
+    let mut union_find = UnionFind::new(n_nodes);
+    let mut tree = Vec::new();
+
+    loop {
+        // lightest edge leaving each component, indexed by the representative
+        let mut cheapest: Vec<Option<usize>> = vec![None; n_nodes];
+        for (e, &(id1, id2, _)) in edges.iter().enumerate() {
+            let root1 = union_find.find(id1);
+            let root2 = union_find.find(id2);
+            if root1 == root2 {
+                continue;
+            }
+            for root in [root1, root2] {
+                match cheapest[root] {
+                    Some(best) if compare_edges(edges, best, e) != Ordering::Greater => {}
+                    _ => cheapest[root] = Some(e),
+                }
+            }
+        }
+
+        let mut merged = false;
+        for e in cheapest.into_iter().flatten() {
+            let (id1, id2, _) = edges[e];
+            record(&mut trace, e, StepKind::Considered);
+            // the same edge can be chosen by both of its components
+            if union_find.union(id1, id2) {
+                tree.push(e);
+                merged = true;
+                record(&mut trace, e, StepKind::Accepted);
+            } else {
+                record(&mut trace, e, StepKind::Rejected);
+            }
+        }
+        if !merged {
+            return tree;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::test_inputs::random_edges;
+    use crate::generator::Rng;
+
+    fn total(edges: &[WeightedEdge], tree: &[usize]) -> f32 {
+        tree.iter().map(|&e| edges[e].2).sum()
+    }
+
+    /// Every algorithm gives a forest with the same total weight and number of edges
+    fn assert_same_totals(n_nodes: usize, edges: &[WeightedEdge]) {
+        let n_components = Components::new(n_nodes, edges).count();
+        let kruskal = kruskal(n_nodes, edges);
+        for algorithm in SpanningTreeAlgorithm::ALL {
+            let tree = algorithm.run(n_nodes, edges);
+            assert_eq!(tree.len(), n_nodes - n_components, "{algorithm}");
+            let (expected, total) = (total(edges, &kruskal), total(edges, &tree));
+            assert!(
+                (expected - total).abs() <= 1e-3 * expected.abs().max(1.),
+                "{algorithm}: {total} instead of {expected}"
+            );
+        }
+    }
+
+    /// The maximum tree is the minimum one with opposite weights
+    fn opposite(edges: &[WeightedEdge]) -> Vec<WeightedEdge> {
+        edges
+            .iter()
+            .map(|&(i, j, weight)| (i, j, -weight))
+            .collect()
+    }
+
+    #[test]
+    fn same_totals_on_random_connected_graphs() {
+        let mut rng = Rng::new(2);
+        for n_nodes in 1..30 {
+            let edges = random_edges(&mut rng, n_nodes, 2 * n_nodes, true);
+            assert_same_totals(n_nodes, &edges);
+            assert_same_totals(n_nodes, &opposite(&edges));
+        }
+    }
+
+    #[test]
+    fn same_totals_on_forests() {
+        let mut rng = Rng::new(3);
+        for n_nodes in 1..30 {
+            let edges = random_edges(&mut rng, n_nodes, n_nodes / 2, false);
+            assert_same_totals(n_nodes, &edges);
+            assert_same_totals(n_nodes, &opposite(&edges));
+        }
+        // isolated nodes and no edge at all
+        assert_same_totals(5, &[]);
+    }
+
+    #[test]
+    fn same_totals_with_tied_weights() {
+        let mut rng = Rng::new(4);
+        for n_nodes in 1..30 {
+            let edges: Vec<WeightedEdge> = random_edges(&mut rng, n_nodes, 3 * n_nodes, true)
+                .into_iter()
+                .map(|(i, j, weight)| (i, j, (weight / 25.).floor()))
+                .collect();
+            assert_same_totals(n_nodes, &edges);
+            assert_same_totals(n_nodes, &opposite(&edges));
+        }
+        // a square with equal sides and parallel edges
+        let square = [(0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 0, 1.), (0, 1, 1.)];
+        assert_same_totals(4, &square);
+    }
+
+    #[test]
+    fn the_maximum_tree_is_the_heaviest() {
+        // a triangle where the minimum tree skips the heaviest edge and the maximum the lightest
+        let edges = [(0, 1, 1.), (1, 2, 2.), (0, 2, 3.)];
+        for algorithm in SpanningTreeAlgorithm::ALL {
+            assert_eq!(total(&edges, &algorithm.run(3, &edges)), 3.);
+            assert_eq!(total(&edges, &algorithm.run(3, &opposite(&edges))), 5.);
+        }
+    }
+
+    /// Edges of a component alone, with its nodes numbered from 0
+    fn component_edges(
+        components: &Components,
+        label: usize,
+        edges: &[WeightedEdge],
+    ) -> Vec<WeightedEdge> {
+        let members = components.members(label);
+        let index = |id: usize| members.iter().position(|&member| member == id).unwrap();
+        edges
+            .iter()
+            .filter(|&&(id1, _, _)| components.label(id1) == label)
+            .map(|&(id1, id2, weight)| (index(id1), index(id2), weight))
+            .collect()
+    }
+
+    #[test]
+    fn every_tree_of_the_forest_is_minimal() {
+        let mut rng = Rng::new(15);
+        for n_nodes in 1..30 {
+            // a few edges for many components, some of them isolated nodes
+            let edges = random_edges(&mut rng, n_nodes, n_nodes * 2 / 3, false);
+            for algorithm in SpanningTreeAlgorithm::ALL {
+                let forest = SpanningForest::new(n_nodes, &edges, algorithm.run(n_nodes, &edges));
+                let components = &forest.components;
+                assert_eq!(forest.totals.len(), components.count());
+                assert_eq!(forest.edges.len(), n_nodes - components.count());
+                assert_eq!(forest.is_tree(), components.count() == 1);
+                for label in 0..components.count() {
+                    let alone = component_edges(components, label, &edges);
+                    let size = components.sizes()[label];
+                    let expected = total(&alone, &kruskal(size, &alone));
+                    assert!(
+                        (forest.totals[label] - expected).abs() <= 1e-3,
+                        "{algorithm}: {} instead of {expected}",
+                        forest.totals[label]
+                    );
+                }
+                let expected: f32 = forest.totals.iter().sum();
+                assert!((forest.total() - expected).abs() <= 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn forest_of_isolated_nodes() {
+        let forest = SpanningForest::new(3, &[], Vec::new());
+        assert_eq!(forest.totals, [0., 0., 0.]);
+        assert_eq!(forest.total(), 0.);
+        assert!(!forest.is_tree());
+
+        // 0 - 1 and 2 - 3 with a heavier parallel edge, 4 alone
+        let edges = [(0, 1, 2.), (3, 2, 1.), (2, 3, 5.)];
+        for algorithm in SpanningTreeAlgorithm::ALL {
+            let forest = SpanningForest::new(5, &edges, algorithm.run(5, &edges));
+            assert_eq!(forest.totals, [2., 1., 0.], "{algorithm}");
+            assert_eq!(forest.total(), 3.);
+        }
+    }
+
+    #[test]
+    fn the_trace_accepts_the_edges_of_the_tree() {
+        let mut rng = Rng::new(16);
+        for n_nodes in 1..30 {
+            let edges = random_edges(&mut rng, n_nodes, n_nodes, n_nodes % 2 == 0);
+            let count = Components::new(n_nodes, &edges).count();
+            for algorithm in SpanningTreeAlgorithm::ALL {
+                let steps = algorithm.trace(n_nodes, &edges);
+                let accepted: Vec<usize> = steps
+                    .iter()
+                    .filter(|step| step.kind == StepKind::Accepted)
+                    .map(|step| step.edge)
+                    .collect();
+                assert_eq!(accepted, algorithm.run(n_nodes, &edges), "{algorithm}");
+                assert_eq!(n_components(n_nodes, &steps), count);
+                assert_eq!(n_components(n_nodes, &[]), n_nodes);
+                // every edge is considered before being accepted or rejected
+                for pair in steps.windows(2) {
+                    if pair[1].kind != StepKind::Considered {
+                        assert_eq!(pair[0].kind, StepKind::Considered, "{algorithm}");
+                        assert_eq!(pair[0].edge, pair[1].edge, "{algorithm}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ pub enum State {
     Dragging,
     RightClicked,
     AddNode,
+    /// Drawing a rectangle to select the nodes inside
+    Selecting,
 }
 
 impl std::fmt::Display for State {
@@ -14,6 +16,7 @@ impl std::fmt::Display for State {
             State::Dragging => write!(f, "Dragging")?,
             State::RightClicked => write!(f, "RightClicked")?,
             State::AddNode => write!(f, "AddNode")?,
+            State::Selecting => write!(f, "Selecting")?,
         }
         Ok(())
     }
@@ -0,0 +1,162 @@
+/// Disjoint-set structure over the elements `0..len`
+/// using union by rank and path compression
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    count: usize,
+}
+
+impl UnionFind {
+    // CONSTRUCTOR /////////////////////////////////////////////////////////////////
+    /// Every element starts in its own set
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            count: len,
+        }
+    }
+
+    // GETTERS //////////////////////////////////////////////////////////////////////
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Representative of the set containing `x`
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // path compression: every node on the way points directly to the root
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Checks if `x` and `y` are in the same set
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    // SETTERS //////////////////////////////////////////////////////////////////////
+    /// Adds a new element in its own set and returns it
+    pub fn push(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.rank.push(0);
+        self.count += 1;
+        id
+    }
+
+    /// Merges the sets containing `x` and `y`
+    /// returns false if they were already in the same set
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
+        // union by rank: the shallowest tree goes under the deepest one
+        match self.rank[root_x].cmp(&self.rank[root_y]) {
+            std::cmp::Ordering::Less => self.parent[root_x] = root_y,
+            std::cmp::Ordering::Greater => self.parent[root_y] = root_x,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_y] = root_x;
+                self.rank[root_x] += 1;
+            }
+        }
+        self.count -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_element_starts_alone() {
+        let mut sets = UnionFind::new(4);
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets.count(), 4);
+        for x in 0..4 {
+            assert_eq!(sets.find(x), x);
+            for y in 0..4 {
+                assert_eq!(sets.connected(x, y), x == y);
+            }
+        }
+        let empty = UnionFind::new(0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.count(), 0);
+    }
+
+    #[test]
+    fn union_joins_the_sets_once() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert_eq!(sets.count(), 3);
+        // already joined, directly or through the others
+        assert!(!sets.union(1, 0));
+        assert!(!sets.union(0, 3));
+        assert!(!sets.union(4, 4));
+        assert_eq!(sets.count(), 3);
+
+        for x in 0..4 {
+            assert!(sets.connected(x, 0));
+            assert_eq!(sets.find(x), sets.find(3));
+        }
+        assert!(!sets.connected(0, 4));
+        assert!(!sets.connected(4, 5));
+        assert!(sets.union(5, 4));
+        assert!(sets.connected(4, 5));
+        assert_eq!(sets.count(), 2);
+    }
+
+    #[test]
+    fn push_adds_a_set() {
+        let mut sets = UnionFind::new(2);
+        sets.union(0, 1);
+        assert_eq!(sets.push(), 2);
+        assert_eq!(sets.len(), 3);
+        assert_eq!(sets.count(), 2);
+        assert!(!sets.connected(2, 0));
+        assert!(sets.union(2, 0));
+        assert_eq!(sets.count(), 1);
+
+        let mut sets = UnionFind::default();
+        assert_eq!(sets.push(), 0);
+        assert_eq!(sets.push(), 1);
+        assert_eq!(sets.count(), 2);
+    }
+
+    #[test]
+    fn a_long_chain_is_one_set() {
+        let n = 1000;
+        let mut sets = UnionFind::new(n);
+        for x in 1..n {
+            assert!(sets.union(x - 1, x));
+        }
+        assert_eq!(sets.count(), 1);
+        let root = sets.find(0);
+        assert!((0..n).all(|x| sets.find(x) == root));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::camera::Camera;
+use crate::highlight::Highlight;
 use crate::image::Image;
 use crate::node::Node;
+use crate::save::{TextureNames, VertexData, VertexStyle};
 
 #[derive(Debug, Clone)]
 enum VertexDrawable {
@@ -22,45 +25,27 @@ impl std::fmt::Display for VertexDrawable {
     }
 }
 
+/// Way a vertex is drawn, its weight is the one of its edge in the model of the graph
 #[derive(Debug, Clone)]
 pub struct Vertex {
     node_id1: usize,
     node_id2: usize,
     drawable: VertexDrawable,
+    highlight: Highlight,
+    /// Font size of the weight label, no label is drawn when it is `None`
+    label_size: Option<f32>,
 }
 
 impl Vertex {
     // CONSTRUCTORS /////////////////////////////////////////////////////////////////
+    #[allow(clippy::unwrap_or_default)]
     pub fn new(node_id1: usize, node_id2: usize, stroke: Option<egui::Stroke>) -> Self {
         Self {
             node_id1,
             node_id2,
-            drawable: VertexDrawable::Straight(
-                stroke.unwrap_or(egui::Stroke::default()),
-            ),
-        }
-    }
-
-    pub fn new_straight_vertex(node_id1: usize, node_id2: usize, stroke: egui::Stroke) -> Self {
-        Self {
-            node_id1,
-            node_id2,
-            drawable: VertexDrawable::Straight(stroke),
-        }
-    }
-
-    pub fn new_cobblestone_vertex(
-        node_id1: usize,
-        node_id2: usize,
-        textures_id: Vec<egui::TextureId>,
-    ) -> Self {
-        Self {
-            node_id1,
-            node_id2,
-            drawable: VertexDrawable::Cobblestone {
-                textures_id,
-                width: 50.0,
-            },
+            drawable: VertexDrawable::Straight(stroke.unwrap_or(egui::Stroke::default())),
+            highlight: Highlight::None,
+            label_size: None,
         }
     }
 
@@ -73,7 +58,26 @@ impl Vertex {
         self.node_id2
     }
 
+    pub fn is_cobblestone(&self) -> bool {
+        matches!(self.drawable, VertexDrawable::Cobblestone { .. })
+    }
+
+    fn half_width(&self) -> f32 {
+        match &self.drawable {
+            VertexDrawable::Straight(stroke) => stroke.width / 2.,
+            VertexDrawable::Cobblestone { width, .. } => width / 2.,
+        }
+    }
+
     // SETTER //////////////////////////////////////////////////////////////////////
+    pub fn set_highlight(&mut self, highlight: Highlight) {
+        self.highlight = highlight;
+    }
+
+    pub fn set_label_size(&mut self, label_size: Option<f32>) {
+        self.label_size = label_size;
+    }
+
     pub fn set_stroke(&mut self, stroke: egui::Stroke) {
         match self.drawable {
             VertexDrawable::Straight(ref mut s) => *s = stroke,
@@ -88,6 +92,11 @@ impl Vertex {
         }
     }
 
+    /// Draw the vertex as a line again after it was drawn with textures
+    pub fn set_straight(&mut self, stroke: egui::Stroke) {
+        self.drawable = VertexDrawable::Straight(stroke);
+    }
+
     pub fn set_textures(&mut self, textures_id: Vec<egui::TextureId>) {
         self.drawable = VertexDrawable::Cobblestone {
             textures_id,
@@ -102,13 +111,65 @@ impl Vertex {
         }
     }
 
-    pub fn set_color (&mut self, color: egui::Color32) {
+    pub fn set_color(&mut self, color: egui::Color32) {
         match self.drawable {
             VertexDrawable::Straight(ref mut s) => s.color = color,
             VertexDrawable::Cobblestone { .. } => {}
         }
     }
 
+    // SAVE ////////////////////////////////////////////////////////////////////////
+    pub fn to_data(
+        &self,
+        weight: Option<f32>,
+        textures: &(impl TextureNames + ?Sized),
+    ) -> VertexData {
+        let style = match &self.drawable {
+            VertexDrawable::Straight(stroke) => VertexStyle::Straight((*stroke).into()),
+            VertexDrawable::Cobblestone { textures_id, width } => VertexStyle::Cobblestone {
+                textures: textures_id
+                    .iter()
+                    .filter_map(|&texture_id| textures.name(texture_id))
+                    .collect(),
+                width: *width,
+            },
+        };
+        VertexData {
+            node_id1: self.node_id1,
+            node_id2: self.node_id2,
+            weight,
+            style,
+        }
+    }
+
+    /// Vertex saved in `data`, drawn straight if none of its textures are found
+    pub fn from_data(data: &VertexData, textures: &(impl TextureNames + ?Sized)) -> Self {
+        let mut vertex = Vertex::new(data.node_id1, data.node_id2, None);
+        match &data.style {
+            VertexStyle::Straight(stroke) => vertex.set_stroke((*stroke).into()),
+            VertexStyle::Cobblestone {
+                textures: names,
+                width,
+            } => {
+                let textures_id: Vec<egui::TextureId> = names
+                    .iter()
+                    .filter_map(|name| textures.texture_id(name))
+                    .collect();
+                if !textures_id.is_empty() {
+                    vertex.set_textures(textures_id);
+                    vertex.set_width_cobblestone(*width);
+                }
+            }
+        }
+        vertex
+    }
+
+    /// Checks if a point is on the vertex drawn between `pos1` and `pos2`
+    pub fn contains(&self, point: egui::Pos2, pos1: egui::Pos2, pos2: egui::Pos2) -> bool {
+        // never less than a few pixels so that thin vertices can still be clicked
+        distance_to_segment(point, pos1, pos2) <= self.half_width().max(5.)
+    }
+
     /*
     pub fn update(&mut self, pos1: egui::Pos2, pos2: egui::Pos2) {
         match &mut self.drawable {
@@ -130,7 +191,19 @@ impl Vertex {
     }
     */
 
-    pub fn draw(&self, ui: &mut egui::Ui, pos1: egui::Pos2, pos2: egui::Pos2) {
+    /// Copy of the vertex with its widths multiplied by `zoom`
+    pub fn scaled(&self, zoom: f32) -> Self {
+        let mut vertex = self.clone();
+        match vertex.drawable {
+            VertexDrawable::Straight(ref mut stroke) => stroke.width *= zoom,
+            VertexDrawable::Cobblestone { ref mut width, .. } => *width *= zoom,
+        }
+        vertex
+    }
+
+    /// Draw the vertex between `pos1` and `pos2`
+    /// `weight` is written next to it if the vertex has a label
+    pub fn draw(&self, ui: &mut egui::Ui, pos1: egui::Pos2, pos2: egui::Pos2, weight: f32) {
         match &self.drawable {
             VertexDrawable::Straight(stroke) => {
                 ui.painter().line_segment([pos1, pos2], *stroke);
@@ -190,11 +263,71 @@ impl Vertex {
                 for i in 0..(n_cobblestone + 1) {
                     let pos = clean_pos1
                         + (clean_pos2 - clean_pos1) * ((i + 1) as f32 / (n_cobblestone + 2) as f32);
-                    Image::new(pos, size, textures_id[(i % n_textures as i32) as usize]) //.draw(ui);
-                        .draw_rotate_center(ui, angle);
+                    Image::new(size, textures_id[(i % n_textures as i32) as usize]) //.draw(ui);
+                        .draw_rotate_center(ui, pos, angle);
                 }
             }
         }
+
+        // draw the highlight on top of the vertex
+        if let Some(color) = self.highlight.color() {
+            let width = match &self.drawable {
+                VertexDrawable::Straight(stroke) => stroke.width + 4.0,
+                VertexDrawable::Cobblestone { width, .. } => width / 4.0,
+            };
+            ui.painter()
+                .line_segment([pos1, pos2], egui::Stroke::new(width, color));
+        }
+
+        if let Some(label_size) = self.label_size {
+            self.draw_label(ui, pos1, pos2, weight, label_size);
+        }
+    }
+
+    /// Draw a wide line under the vertex to show that it is hovered or selected
+    pub fn draw_halo(
+        &self,
+        ui: &mut egui::Ui,
+        pos1: egui::Pos2,
+        pos2: egui::Pos2,
+        color: egui::Color32,
+    ) {
+        let width = 2. * self.half_width().max(5.) + 6.;
+        ui.painter()
+            .line_segment([pos1, pos2], egui::Stroke::new(width, color));
+    }
+
+    /// Draw the weight in a box next to the middle of the vertex
+    fn draw_label(
+        &self,
+        ui: &mut egui::Ui,
+        pos1: egui::Pos2,
+        pos2: egui::Pos2,
+        weight: f32,
+        label_size: f32,
+    ) {
+        let half_width = self.half_width();
+        let galley = ui.painter().layout_no_wrap(
+            format!("{weight:.1}"),
+            egui::FontId::proportional(label_size),
+            ui.visuals().text_color(),
+        );
+        let margin = egui::Vec2::splat(label_size / 4.);
+        let size = galley.size() + 2. * margin;
+
+        // the label is moved perpendicular to the vertex, always above it
+        let mut normal = (pos2 - pos1).normalized().rot90();
+        if normal.y > 0. {
+            normal = -normal;
+        }
+        // enough to not cover the vertex whatever its direction
+        let offset = half_width + (normal.x.abs() * size.x + normal.y.abs() * size.y) / 2.;
+        let center = pos1 + (pos2 - pos1) / 2. + normal * offset;
+
+        let rect = egui::Rect::from_center_size(center, size);
+        ui.painter()
+            .rect_filled(rect, label_size / 4., ui.visuals().extreme_bg_color);
+        ui.painter().galley(rect.min + margin, galley);
     }
 }
 
@@ -204,18 +337,31 @@ impl std::fmt::Display for Vertex {
             f,
             "({}, {}): {}",
             self.node_id1, self.node_id2, self.drawable
-        )
+        )?;
+        Ok(())
     }
 }
 
+/// Distance between a point and the segment `[pos1, pos2]`
+pub fn distance_to_segment(point: egui::Pos2, pos1: egui::Pos2, pos2: egui::Pos2) -> f32 {
+    let segment = pos2 - pos1;
+    let length_sq = segment.length_sq();
+    if length_sq == 0. {
+        return point.distance(pos1);
+    }
+    let t = ((point - pos1).dot(segment) / length_sq).clamp(0., 1.);
+    point.distance(pos1 + segment * t)
+}
+
 /// Temporary vertex used to draw a new vertex
 /// in a graph without using a full Vertex
 #[derive(Debug, Clone)]
 pub struct TemporaryVertex {
     pub node_selected_id1: Option<usize>,
     pub node_selected_id2: Option<usize>,
-    node1: Option<Node>,
-    node2: Option<Node>,
+    /// Nodes drawn at the ends, the first one follows the mouse
+    node1: Option<(Node, egui::Pos2)>,
+    node2: Option<(Node, egui::Pos2)>,
     stroke: egui::Stroke,
 }
 
@@ -234,19 +380,19 @@ impl Default for TemporaryVertex {
 impl TemporaryVertex {
     // Getters ///////////////////////////////////////////////////////////
     pub fn _first(&self) -> Option<&Node> {
-        self.node1.as_ref()
+        self.node1.as_ref().map(|(node, _)| node)
     }
 
     pub fn first_mut(&mut self) -> Option<&mut Node> {
-        self.node1.as_mut()
+        self.node1.as_mut().map(|(node, _)| node)
     }
 
     pub fn _second(&self) -> Option<&Node> {
-        self.node2.as_ref()
+        self.node2.as_ref().map(|(node, _)| node)
     }
 
     pub fn second_mut(&mut self) -> Option<&mut Node> {
-        self.node2.as_mut()
+        self.node2.as_mut().map(|(node, _)| node)
     }
 
     pub fn first_is_some(&self) -> bool {
@@ -269,26 +415,30 @@ impl TemporaryVertex {
         self.stroke.width = width;
     }
 
+    pub fn follow_mouse(&mut self, event: &egui::Event) {
+        if let (Some((_, pos1)), egui::Event::PointerMoved(pos)) = (self.node1.as_mut(), event) {
+            *pos1 = *pos;
+        }
+    }
+
     // Draw /////////////////////////////////////////////////////////////
-    pub fn draw(&self, ui: &mut egui::Ui) {
-        ui.painter().line_segment(
-            [
-                self.node1.as_ref().unwrap().pos(),
-                self.node2.as_ref().unwrap().pos(),
-            ],
-            self.stroke,
-        );
-        self.node1.as_ref().unwrap().draw(ui);
-        self.node2.as_ref().unwrap().draw(ui);
+    pub fn draw(&self, ui: &mut egui::Ui, camera: &Camera) {
+        let (node1, pos1) = self.node1.unwrap();
+        let (node2, pos2) = self.node2.unwrap();
+        let (pos1, pos2) = (camera.to_screen(pos1), camera.to_screen(pos2));
+        let stroke = egui::Stroke::new(self.stroke.width * camera.zoom(), self.stroke.color);
+        ui.painter().line_segment([pos1, pos2], stroke);
+        node1.scaled(camera.zoom()).draw(ui, pos1);
+        node2.scaled(camera.zoom()).draw(ui, pos2);
     }
-    pub fn select_first(&mut self, node: Node) {
-        self.node_selected_id1 = Some(node.id());
-        self.node1 = Some(Node::new_circle_node(Some(0), node.pos(), self.stroke));
+    pub fn select_first(&mut self, id: usize, pos: egui::Pos2) {
+        self.node_selected_id1 = Some(id);
+        self.node1 = Some((Node::new_circle_node(Some(0), self.stroke), pos));
     }
 
-    pub fn select_second(&mut self, node: Node) {
-        self.node_selected_id2 = Some(node.id());
-        self.node2 = Some(Node::new_circle_node(Some(0), node.pos(), self.stroke));
+    pub fn select_second(&mut self, id: usize, pos: egui::Pos2) {
+        self.node_selected_id2 = Some(id);
+        self.node2 = Some((Node::new_circle_node(Some(0), self.stroke), pos));
     }
 }
 
@@ -297,13 +447,13 @@ impl std::fmt::Display for TemporaryVertex {
         write!(f, "First node: ")?;
         // print "None" if node1 is None else print node1
         match &self.node1 {
-            Some(node) => write!(f, "{} ", node)?,
+            Some((node, _)) => write!(f, "{} ", node)?,
             None => write!(f, "None ")?,
         }
         write!(f, "| Second node : ")?;
         // print "None" if node1 is None else print node1
         match &self.node1 {
-            Some(node) => writeln!(f, "{}", node)?,
+            Some((node, _)) => writeln!(f, "{}", node)?,
             None => writeln!(f, "None")?,
         }
         Ok(())
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// How the weight of a vertex is computed by every algorithm
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Weighting {
+    /// Distance between the two nodes of the vertex
+    #[default]
+    Geometric,
+    /// Weight given to the vertex, the distance is used if it has none
+    Explicit,
+}
+
+impl Weighting {
+    pub const ALL: [Weighting; 2] = [Weighting::Geometric, Weighting::Explicit];
+
+    /// Weight of a vertex between two nodes at `distance`, with an optional explicit weight
+    pub fn weight(self, distance: f32, explicit: Option<f32>) -> f32 {
+        match self {
+            Weighting::Geometric => distance,
+            Weighting::Explicit => explicit.unwrap_or(distance),
+        }
+    }
+}
+
+impl std::fmt::Display for Weighting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Weighting::Geometric => write!(f, "Geometric")?,
+            Weighting::Explicit => write!(f, "Explicit")?,
+        }
+        Ok(())
+    }
+}
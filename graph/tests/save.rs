@@ -0,0 +1,176 @@
+#![cfg(feature = "gui")]
+use graph::graph::Graph;
+use graph::save::GraphData;
+use graph::spanning_tree::SpanningTreeAlgorithm;
+use graph::weighting::Weighting;
+
+fn textures() -> Vec<(String, egui::TextureId)> {
+    vec![
+        ("house1".to_string(), egui::TextureId::User(1)),
+        ("cobblestone1".to_string(), egui::TextureId::User(2)),
+    ]
+}
+
+fn town() -> Graph {
+    let mut graph = Graph::default();
+    let stroke = egui::Stroke::new(2.0, egui::Color32::GREEN);
+    for (x, y) in [(0., 0.), (100., 0.), (100., 80.), (0., 120.), (250., 40.)] {
+        graph.add_node(egui::Pos2::new(x, y), stroke);
+    }
+    for (id1, id2) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 4), (2, 4)] {
+        graph.add_edge(id1, id2);
+    }
+    graph
+        .set_weight(0, 2, Some(1.0))
+        .set_weight(2, 4, Some(500.0))
+        .set_texture_node(
+            1,
+            100.0,
+            egui::TextureId::User(1),
+            egui::Vec2::new(50., 40.),
+        );
+    graph
+}
+
+fn round_trip(graph: &Graph) -> Graph {
+    let json = graph.to_json(textures().as_slice()).unwrap();
+    Graph::from_json(&json, textures().as_slice()).unwrap()
+}
+
+#[test]
+fn round_trip_keeps_the_data() {
+    let mut graph = town();
+    graph.set_textures_vertex(vec![egui::TextureId::User(2)]);
+    let loaded = round_trip(&graph);
+    assert_eq!(
+        loaded.to_data(textures().as_slice()),
+        graph.to_data(textures().as_slice())
+    );
+}
+
+#[test]
+fn round_trip_keeps_the_covering_tree() {
+    for weighting in Weighting::ALL {
+        let mut graph = town();
+        graph.set_weighting(weighting);
+        let loaded = round_trip(&graph);
+        for algorithm in SpanningTreeAlgorithm::ALL {
+            for max in [false, true] {
+                assert_eq!(
+                    loaded.covering_tree(algorithm, max).weighted_edges(),
+                    graph.covering_tree(algorithm, max).weighted_edges()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn unknown_textures_are_not_fatal() {
+    let graph = town();
+    let json = graph.to_json(textures().as_slice()).unwrap();
+    let loaded = Graph::from_json(&json, [].as_slice()).unwrap();
+    assert_eq!(loaded.weighted_edges(), graph.weighted_edges());
+}
+
+#[test]
+fn invalid_vertices_are_rejected() {
+    let json = r#"{
+        "nodes": [{ "id": 0, "pos": [0.0, 0.0], "style": { "Circle": { "radius": 10.0, "stroke": { "width": 1.0, "color": [255, 0, 0, 255] } } } }],
+        "adjacencies": [[]],
+        "vertices": [{ "node_id1": 0, "node_id2": 3, "style": { "Straight": { "width": 1.0, "color": [0, 0, 0, 255] } } }]
+    }"#;
+    assert!(Graph::from_json(json, [].as_slice()).is_err());
+}
+
+/// Error of the data of the town after a change
+fn error_after(change: impl FnOnce(&mut GraphData)) -> String {
+    let mut data = town().to_data(textures().as_slice());
+    assert_eq!(data.check(), Ok(()));
+    change(&mut data);
+    let json = data.to_json().unwrap();
+    assert!(Graph::from_json(&json, textures().as_slice()).is_err());
+    data.check().unwrap_err()
+}
+
+/// Index in the data of the vertex between two nodes
+fn vertex(data: &GraphData, id1: usize, id2: usize) -> usize {
+    data.vertices
+        .iter()
+        .position(|vertex| (vertex.node_id1, vertex.node_id2) == (id1, id2))
+        .unwrap()
+}
+
+#[test]
+fn duplicate_vertices_are_rejected() {
+    let error = error_after(|data| {
+        let mut copy = data.vertices[vertex(data, 0, 1)].clone();
+        copy.weight = Some(2.);
+        data.vertices.push(copy);
+    });
+    assert_eq!(error, "several vertices between 0 and 1");
+
+    // the same vertex in the other direction
+    let error = error_after(|data| {
+        let mut copy = data.vertices[vertex(data, 0, 1)].clone();
+        (copy.node_id1, copy.node_id2) = (1, 0);
+        data.vertices.push(copy);
+    });
+    assert_eq!(error, "several vertices between 1 and 0");
+}
+
+#[test]
+fn loops_are_rejected() {
+    let error = error_after(|data| {
+        let mut copy = data.vertices[0].clone();
+        (copy.node_id1, copy.node_id2) = (3, 3);
+        data.vertices.push(copy);
+        data.adjacencies[3].push(3);
+    });
+    assert_eq!(error, "vertex between 3 and itself");
+}
+
+#[test]
+fn negative_and_infinite_weights_are_rejected() {
+    let error = error_after(|data| {
+        let index = vertex(data, 0, 1);
+        data.vertices[index].weight = Some(-5.);
+    });
+    assert_eq!(error, "invalid weight -5 of the vertex between 0 and 1");
+
+    // JSON has no infinite weight, but the data may not come from a file
+    let mut data = town().to_data(textures().as_slice());
+    let index = vertex(&data, 0, 1);
+    for weight in [f32::INFINITY, f32::NAN] {
+        data.vertices[index].weight = Some(weight);
+        assert_eq!(
+            data.check(),
+            Err(format!(
+                "invalid weight {weight} of the vertex between 0 and 1"
+            ))
+        );
+    }
+    // a weight of zero is a free road
+    data.vertices[index].weight = Some(0.);
+    assert_eq!(data.check(), Ok(()));
+}
+
+#[test]
+fn adjacencies_without_vertex_are_rejected() {
+    let error = error_after(|data| {
+        data.vertices.remove(vertex(data, 1, 4));
+    });
+    assert_eq!(error, "no vertex between 1 and 4");
+
+    let error = error_after(|data| {
+        data.adjacencies[3].push(4);
+        data.adjacencies[4].push(3);
+    });
+    assert_eq!(error, "no vertex between 3 and 4");
+
+    let error = error_after(|data| {
+        data.adjacencies[0].push(1);
+        data.adjacencies[1].push(0);
+    });
+    assert_eq!(error, "several adjacencies between 0 and 1");
+}
@@ -1,3 +1,112 @@
+#![warn(clippy::all, rust_2018_idioms)]
+mod tikz;
+
+use graph::model::Model;
+use tikz::{graph_to_tikz, Options, Tree};
+
+const USAGE: &str = "Usage: graph_to_latex [OPTIONS] <graph.json>
+
+Print a graph saved by the covering tree app as a TikZ picture.
+
+Options:
+    -o, --output <file>   write the picture in a file instead of the standard output
+    -s, --scale <pixels>  number of pixels of the canvas in one centimeter (default 50)
+    -w, --weights         write the weight of every edge
+    -t, --tree            highlight the edges of the minimum covering tree
+        --max-tree        highlight the edges of the maximum covering tree
+        --standalone      make a whole document that can be compiled
+    -h, --help            print this help";
+
+/// Command line arguments
+#[derive(Debug)]
+struct Args {
+    input: String,
+    output: Option<String>,
+    options: Options,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut input = None;
+    let mut output = None;
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            "-o" | "--output" => {
+                output = Some(args.next().ok_or("missing file after --output")?);
+            }
+            "-s" | "--scale" => {
+                let scale = args.next().ok_or("missing number after --scale")?;
+                options.scale = match scale.parse::<f32>() {
+                    Ok(scale) if scale > 0.0 && scale.is_finite() => scale,
+                    _ => return Err(format!("invalid scale '{scale}'")),
+                };
+            }
+            "-w" | "--weights" => options.weights = true,
+            "-t" | "--tree" => options.tree = Tree::Min,
+            "--max-tree" => options.tree = Tree::Max,
+            "--standalone" => options.standalone = true,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    Ok(Args {
+        input: input.ok_or("missing graph file")?,
+        output,
+        options,
+    })
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let json =
+        std::fs::read_to_string(&args.input).map_err(|error| format!("{}: {error}", args.input))?;
+    // the textures are not drawn in LaTeX
+    let graph: Model =
+        Model::from_json(&json).map_err(|error| format!("{}: {error}", args.input))?;
+
+    let tikz = graph_to_tikz(&graph, &args.options);
+    match args.output {
+        Some(output) => std::fs::write(&output, tikz).map_err(|error| format!("{output}: {error}")),
+        None => {
+            print!("{tikz}");
+            Ok(())
+        }
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let result = parse_args(std::env::args().skip(1)).and_then(run);
+    if let Err(error) = result {
+        eprintln!("error: {error}\n\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn the_scale_is_a_positive_number() {
+        let args = parse(&["-s", "25", "graph.json"]).unwrap();
+        assert_eq!(args.options.scale, 25.);
+        assert_eq!(args.input, "graph.json");
+        for scale in ["0", "-10", "inf", "-inf", "NaN", "ten"] {
+            assert_eq!(
+                parse(&["--scale", scale, "graph.json"]).unwrap_err(),
+                format!("invalid scale '{scale}'")
+            );
+        }
+        assert!(parse(&["graph.json", "-s"]).is_err());
+    }
 }
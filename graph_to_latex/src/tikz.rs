@@ -0,0 +1,245 @@
+use graph::model::Model;
+use graph::spanning_tree::SpanningTreeAlgorithm;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Which covering tree is drawn on top of the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tree {
+    None,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Number of canvas pixels in one centimeter
+    pub scale: f32,
+    pub weights: bool,
+    pub tree: Tree,
+    pub standalone: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            scale: 50.0,
+            weights: false,
+            tree: Tree::None,
+            standalone: false,
+        }
+    }
+}
+
+/// Coordinate rounded to the 2 decimals that are written, adding `0.` turns -0 into 0
+/// so that a coordinate rounded to zero is not written -0.00
+fn rounded(coordinate: f32) -> f32 {
+    (coordinate * 100.).round() / 100. + 0.
+}
+
+/// TikZ picture of the graph, as a snippet or as a whole document
+pub fn graph_to_tikz<N, E>(graph: &Model<N, E>, options: &Options) -> String {
+    let tree_edges: HashSet<(usize, usize)> = match options.tree {
+        Tree::None => HashSet::new(),
+        Tree::Min | Tree::Max => graph
+            .covering_tree_edges(SpanningTreeAlgorithm::Kruskal, options.tree == Tree::Max)
+            .into_iter()
+            .map(|e| &graph.edges()[e])
+            .map(|edge| (edge.id1.min(edge.id2), edge.id1.max(edge.id2)))
+            .collect(),
+    };
+
+    // writing in a String never fails
+    let mut tikz = String::new();
+    if options.standalone {
+        writeln!(tikz, "\\documentclass[tikz]{{standalone}}").unwrap();
+        writeln!(tikz, "\\begin{{document}}").unwrap();
+    }
+    writeln!(tikz, "\\begin{{tikzpicture}}[").unwrap();
+    writeln!(
+        tikz,
+        "  house/.style={{circle, draw, minimum size=6mm, inner sep=0pt}},"
+    )
+    .unwrap();
+    writeln!(tikz, "  road/.style={{draw, gray}},").unwrap();
+    writeln!(tikz, "  tree/.style={{draw, ultra thick, red}},").unwrap();
+    writeln!(
+        tikz,
+        "  weight/.style={{midway, sloped, above, font=\\small}},"
+    )
+    .unwrap();
+    writeln!(tikz, "]").unwrap();
+
+    for node in graph.nodes() {
+        let id = node.id;
+        // the y axis of the canvas goes down, the one of TikZ goes up
+        let x = rounded(node.pos.0 / options.scale);
+        let y = rounded(-node.pos.1 / options.scale);
+        writeln!(
+            tikz,
+            "  \\node[house] (n{id}) at ({x:.2}, {y:.2}) {{{id}}};"
+        )
+        .unwrap();
+    }
+
+    for (id1, id2, weight) in graph.weighted_edges() {
+        let style = if tree_edges.contains(&(id1.min(id2), id1.max(id2))) {
+            "tree"
+        } else {
+            "road"
+        };
+        let label = if options.weights {
+            format!(" node[weight] {{{weight:.1}}}")
+        } else {
+            String::new()
+        };
+        writeln!(tikz, "  \\draw[{style}] (n{id1}) --{label} (n{id2});").unwrap();
+    }
+
+    writeln!(tikz, "\\end{{tikzpicture}}").unwrap();
+    if options.standalone {
+        writeln!(tikz, "\\end{{document}}").unwrap();
+    }
+    tikz
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::weighting::Weighting;
+
+    const HEADER: &str = "\\begin{tikzpicture}[
+  house/.style={circle, draw, minimum size=6mm, inner sep=0pt},
+  road/.style={draw, gray},
+  tree/.style={draw, ultra thick, red},
+  weight/.style={midway, sloped, above, font=\\small},
+]
+";
+
+    const NODES: &str = "  \\node[house] (n0) at (0.00, 0.00) {0};
+  \\node[house] (n1) at (2.00, 0.00) {1};
+  \\node[house] (n2) at (1.00, -1.50) {2};
+";
+
+    /// Triangle whose minimum tree skips the edge 0-2 and maximum tree the edge 0-1
+    fn triangle() -> Model {
+        let mut model = Model::default();
+        model.set_weighting(Weighting::Explicit);
+        for pos in [(0., 0.), (100., 0.), (50., 75.)] {
+            model.add_node(pos);
+        }
+        for (id1, id2, weight) in [(0, 1, 1.), (1, 2, 2.), (0, 2, 3.)] {
+            model.add_edge(id1, id2);
+            model.set_weight(id1, id2, Some(weight));
+        }
+        model
+    }
+
+    fn picture(edges: &str) -> String {
+        format!("{HEADER}{NODES}{edges}\\end{{tikzpicture}}\n")
+    }
+
+    #[test]
+    fn snippet_with_the_nodes_and_edges() {
+        assert_eq!(
+            graph_to_tikz(&triangle(), &Options::default()),
+            picture(
+                "  \\draw[road] (n0) -- (n1);
+  \\draw[road] (n1) -- (n2);
+  \\draw[road] (n0) -- (n2);
+"
+            )
+        );
+    }
+
+    #[test]
+    fn weights_on_the_edges() {
+        let options = Options {
+            weights: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph_to_tikz(&triangle(), &options),
+            picture(
+                "  \\draw[road] (n0) -- node[weight] {1.0} (n1);
+  \\draw[road] (n1) -- node[weight] {2.0} (n2);
+  \\draw[road] (n0) -- node[weight] {3.0} (n2);
+"
+            )
+        );
+    }
+
+    #[test]
+    fn highlight_the_minimum_tree() {
+        let options = Options {
+            tree: Tree::Min,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph_to_tikz(&triangle(), &options),
+            picture(
+                "  \\draw[tree] (n0) -- (n1);
+  \\draw[tree] (n1) -- (n2);
+  \\draw[road] (n0) -- (n2);
+"
+            )
+        );
+    }
+
+    #[test]
+    fn highlight_the_maximum_tree() {
+        let options = Options {
+            tree: Tree::Max,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph_to_tikz(&triangle(), &options),
+            picture(
+                "  \\draw[road] (n0) -- (n1);
+  \\draw[tree] (n1) -- (n2);
+  \\draw[tree] (n0) -- (n2);
+"
+            )
+        );
+    }
+
+    #[test]
+    fn standalone_document_around_the_snippet() {
+        let snippet = graph_to_tikz(&triangle(), &Options::default());
+        let options = Options {
+            standalone: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph_to_tikz(&triangle(), &options),
+            format!(
+                "\\documentclass[tikz]{{standalone}}\n\\begin{{document}}\n{snippet}\\end{{document}}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn the_scale_divides_the_positions() {
+        let options = Options {
+            scale: 25.,
+            ..Default::default()
+        };
+        let tikz = graph_to_tikz(&triangle(), &options);
+        assert!(tikz.contains("  \\node[house] (n1) at (4.00, 0.00) {1};\n"));
+        assert!(tikz.contains("  \\node[house] (n2) at (2.00, -3.00) {2};\n"));
+    }
+
+    #[test]
+    fn no_minus_zero() {
+        let mut model: Model = Model::default();
+        for pos in [(-0.1, 0.1), (0.2, -0.2), (-0.4, 0.), (0., 0.)] {
+            model.add_node(pos);
+        }
+        let tikz = graph_to_tikz(&model, &Options::default());
+        assert!(!tikz.contains("-0.00"), "{tikz}");
+        assert!(tikz.contains("  \\node[house] (n0) at (0.00, 0.00) {0};\n"));
+        assert!(tikz.contains("  \\node[house] (n1) at (0.00, 0.00) {1};\n"));
+        assert!(tikz.contains("  \\node[house] (n2) at (-0.01, 0.00) {2};\n"));
+        assert!(tikz.contains("  \\node[house] (n3) at (0.00, 0.00) {3};\n"));
+    }
+}